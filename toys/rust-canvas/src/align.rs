@@ -0,0 +1,171 @@
+use crate::models::{combined_bounds, BoundingBox};
+use crate::{Action, CanvasInner};
+
+// ===== 정렬 / 분배 =====
+
+/// 정렬 기준선
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum AlignEdge {
+    Left,
+    Center,
+    Right,
+    Top,
+    Middle,
+    Bottom,
+}
+
+impl AlignEdge {
+    pub(crate) fn parse(s: &str) -> Option<AlignEdge> {
+        match s {
+            "left" => Some(AlignEdge::Left),
+            "center" => Some(AlignEdge::Center),
+            "right" => Some(AlignEdge::Right),
+            "top" => Some(AlignEdge::Top),
+            "middle" => Some(AlignEdge::Middle),
+            "bottom" => Some(AlignEdge::Bottom),
+            _ => None,
+        }
+    }
+
+    /// 박스에서 이 기준선의 좌표
+    fn value(&self, bb: &BoundingBox) -> f64 {
+        match self {
+            AlignEdge::Left => bb.min_x,
+            AlignEdge::Center => (bb.min_x + bb.max_x) / 2.0,
+            AlignEdge::Right => bb.max_x,
+            AlignEdge::Top => bb.min_y,
+            AlignEdge::Middle => (bb.min_y + bb.max_y) / 2.0,
+            AlignEdge::Bottom => bb.max_y,
+        }
+    }
+
+    fn is_horizontal(&self) -> bool {
+        matches!(self, AlignEdge::Left | AlignEdge::Center | AlignEdge::Right)
+    }
+}
+
+impl CanvasInner {
+    /// 선택된 요소의 (ID, 바운딩 박스) — z-order 순
+    fn selected_bounds(&self) -> Vec<(u32, BoundingBox)> {
+        self.scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .filter_map(|e| e.bounding_box().map(|bb| (e.id, bb)))
+            .collect()
+    }
+
+    /// 정렬 기준 요소: 처음 선택한 요소 (없으면 가장 먼저 그려진 선택 요소)
+    fn key_object(&self) -> Option<u32> {
+        self.selection_anchor
+            .filter(|id| self.selected_ids.contains(id))
+            .or_else(|| {
+                self.scope()
+                    .iter()
+                    .find(|e| self.selected_ids.contains(&e.id))
+                    .map(|e| e.id)
+            })
+    }
+
+    /// 선택 요소 정렬 (relative_to: "selection" | "first"). 이동한 요소가 있으면 true
+    pub(crate) fn align_selected(&mut self, edge: &str, relative_to: &str) -> Result<bool, String> {
+        let edge = AlignEdge::parse(edge).ok_or_else(|| format!("unknown align edge: {}", edge))?;
+        let items = self.selected_bounds();
+        if items.len() < 2 {
+            return Ok(false);
+        }
+
+        let reference = match relative_to {
+            "selection" => combined_bounds(
+                self.scope()
+                    .iter()
+                    .filter(|e| self.selected_ids.contains(&e.id)),
+            ),
+            "first" => {
+                let key = self.key_object();
+                items
+                    .iter()
+                    .find(|(id, _)| Some(*id) == key)
+                    .map(|(_, bb)| bb.clone())
+            }
+            _ => return Err(format!("unknown align reference: {}", relative_to)),
+        };
+        let Some(reference) = reference else {
+            return Ok(false);
+        };
+
+        let target = edge.value(&reference);
+        let moves = items
+            .iter()
+            .map(|(id, bb)| {
+                let d = target - edge.value(bb);
+                if edge.is_horizontal() {
+                    (*id, d, 0.0)
+                } else {
+                    (*id, 0.0, d)
+                }
+            })
+            .collect();
+        Ok(self.translate_each(moves))
+    }
+
+    /// 선택 요소를 같은 간격으로 분배 (axis: "horizontal" | "vertical").
+    /// 선택 영역의 전체 범위는 유지, 3개 이상일 때만 동작
+    pub(crate) fn distribute_selected(&mut self, axis: &str) -> Result<bool, String> {
+        let horizontal = match axis {
+            "horizontal" => true,
+            "vertical" => false,
+            _ => return Err(format!("unknown distribute axis: {}", axis)),
+        };
+        let span = |bb: &BoundingBox| {
+            if horizontal {
+                (bb.min_x, bb.max_x)
+            } else {
+                (bb.min_y, bb.max_y)
+            }
+        };
+
+        let mut items = self.selected_bounds();
+        if items.len() < 3 {
+            return Ok(false);
+        }
+        items.sort_by(|(_, a), (_, b)| span(a).0.total_cmp(&span(b).0));
+
+        let start = span(&items[0].1).0;
+        let end = items
+            .iter()
+            .map(|(_, bb)| span(bb).1)
+            .fold(f64::MIN, f64::max);
+        let total: f64 = items.iter().map(|(_, bb)| span(bb).1 - span(bb).0).sum();
+        let gap = (end - start - total) / (items.len() - 1) as f64;
+
+        let mut cursor = start;
+        let mut moves = Vec::with_capacity(items.len());
+        for (id, bb) in &items {
+            let (lo, hi) = span(bb);
+            let d = cursor - lo;
+            moves.push(if horizontal {
+                (*id, d, 0.0)
+            } else {
+                (*id, 0.0, d)
+            });
+            cursor += hi - lo + gap;
+        }
+        Ok(self.translate_each(moves))
+    }
+
+    /// 요소별 이동을 한 번에 적용하고 하나의 undo 단위로 기록
+    fn translate_each(&mut self, moves: Vec<(u32, f64, f64)>) -> bool {
+        let moves: Vec<(u32, f64, f64)> = moves
+            .into_iter()
+            .filter(|(_, dx, dy)| dx.abs() > 1e-9 || dy.abs() > 1e-9)
+            .collect();
+        if moves.is_empty() {
+            return false;
+        }
+        let action = self.scoped(Action::TranslateEach { moves });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.needs_render = true;
+        true
+    }
+}
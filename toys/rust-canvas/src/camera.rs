@@ -0,0 +1,526 @@
+use serde::{Deserialize, Serialize};
+
+use crate::group::find_element;
+use crate::models::{combined_bounds, BoundingBox};
+use crate::oplog::LogOp;
+use crate::CanvasInner;
+
+// ===== 카메라 전환 애니메이션 =====
+
+/// 기본 전환 시간 (ms)
+pub(crate) const DEFAULT_CAMERA_DURATION_MS: f64 = 300.0;
+
+/// 영역에 맞출 때 화면 가장자리 여백 (스크린 px)
+const FIT_PADDING: f64 = 40.0;
+
+/// 진행 중인 카메라 전환
+pub(crate) struct CameraAnimation {
+    from_zoom: f64,
+    from_center_x: f64,
+    from_center_y: f64,
+    to_zoom: f64,
+    to_pan_x: f64,
+    to_pan_y: f64,
+    /// 첫 프레임에서 채워짐 (epoch 기준 ms)
+    start_time: Option<f64>,
+    duration_ms: f64,
+}
+
+/// 휠 입력 해석 방식
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum WheelMode {
+    /// 노치 단위: 방향만 보고 고정 배율 적용
+    Mouse,
+    /// 연속 입력: deltaY 크기에 비례해 지수적으로 줌
+    Trackpad,
+}
+
+/// 카메라 설정 (JSON으로 부분 지정 가능)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct CameraConfig {
+    pub(crate) min_zoom: f64,
+    pub(crate) max_zoom: f64,
+    /// 마우스 모드에서 휠 한 칸당 배율
+    pub(crate) wheel_factor: f64,
+    /// 트랙패드 모드에서 deltaY 1당 로그 배율
+    pub(crate) trackpad_sensitivity: f64,
+    pub(crate) wheel_mode: WheelMode,
+    /// 줌 인/아웃 명령이 거치는 단계 (비어 있으면 step_factor 배율)
+    pub(crate) zoom_steps: Vec<f64>,
+    pub(crate) step_factor: f64,
+}
+
+impl Default for CameraConfig {
+    fn default() -> CameraConfig {
+        CameraConfig {
+            min_zoom: 0.1,
+            max_zoom: 10.0,
+            wheel_factor: 1.1,
+            trackpad_sensitivity: 0.01,
+            wheel_mode: WheelMode::Mouse,
+            zoom_steps: vec![0.1, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 10.0],
+            step_factor: 1.25,
+        }
+    }
+}
+
+impl CameraConfig {
+    pub(crate) fn clamp_zoom(&self, zoom: f64) -> f64 {
+        zoom.clamp(self.min_zoom, self.max_zoom)
+    }
+
+    /// 휠 deltaY에 해당하는 줌 배율
+    pub(crate) fn wheel_zoom_factor(&self, delta: f64) -> f64 {
+        match self.wheel_mode {
+            WheelMode::Mouse => {
+                if delta < 0.0 {
+                    self.wheel_factor
+                } else {
+                    1.0 / self.wheel_factor
+                }
+            }
+            WheelMode::Trackpad => (-delta * self.trackpad_sensitivity).exp(),
+        }
+    }
+
+    /// 현재 줌에서 한 단계 위/아래 줌
+    pub(crate) fn step_zoom(&self, zoom: f64, up: bool) -> f64 {
+        // 부동소수 오차로 같은 단계에 머무르지 않도록 여유를 둠
+        let eps = 1e-6;
+        let next = if up {
+            self.zoom_steps
+                .iter()
+                .copied()
+                .find(|z| *z > zoom * (1.0 + eps))
+        } else {
+            self.zoom_steps
+                .iter()
+                .rev()
+                .copied()
+                .find(|z| *z < zoom * (1.0 - eps))
+        };
+        let zoom = match next {
+            Some(z) => z,
+            None if self.zoom_steps.is_empty() && up => zoom * self.step_factor,
+            None if self.zoom_steps.is_empty() => zoom / self.step_factor,
+            None => zoom,
+        };
+        self.clamp_zoom(zoom)
+    }
+
+    /// 설정값 검증 및 정규화 (단계 정렬, 범위 밖 단계 제거)
+    fn validate(mut self) -> Result<CameraConfig, String> {
+        if !(self.min_zoom > 0.0 && self.max_zoom >= self.min_zoom) {
+            return Err("zoom limits must satisfy 0 < min_zoom <= max_zoom".to_string());
+        }
+        if self.wheel_factor <= 1.0 || self.step_factor <= 1.0 {
+            return Err("wheel_factor and step_factor must be greater than 1".to_string());
+        }
+        if self.trackpad_sensitivity <= 0.0 {
+            return Err("trackpad_sensitivity must be positive".to_string());
+        }
+        let (min, max) = (self.min_zoom, self.max_zoom);
+        self.zoom_steps
+            .retain(|z| z.is_finite() && *z >= min && *z <= max);
+        self.zoom_steps.sort_by(|a, b| a.total_cmp(b));
+        self.zoom_steps.dedup();
+        Ok(self)
+    }
+}
+
+/// 이름 붙은 카메라 위치 (화면 크기와 무관하도록 화면 중심의 월드 좌표로 저장)
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct Bookmark {
+    pub(crate) name: String,
+    pub(crate) center_x: f64,
+    pub(crate) center_y: f64,
+    pub(crate) zoom: f64,
+}
+
+/// 같은 이름이 있으면 교체, 없으면 끝에 추가
+pub(crate) fn upsert_bookmark(bookmarks: &mut Vec<Bookmark>, bookmark: Bookmark) {
+    match bookmarks.iter_mut().find(|b| b.name == bookmark.name) {
+        Some(existing) => *existing = bookmark,
+        None => bookmarks.push(bookmark),
+    }
+}
+
+/// 이름으로 제거. 제거했으면 true
+pub(crate) fn remove_bookmark(bookmarks: &mut Vec<Bookmark>, name: &str) -> bool {
+    let before = bookmarks.len();
+    bookmarks.retain(|b| b.name != name);
+    bookmarks.len() != before
+}
+
+/// ease-in-out (cubic)
+fn ease_in_out(t: f64) -> f64 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
+
+impl CanvasInner {
+    /// 애니메이션 목표 카메라 (없으면 현재 카메라) — (zoom, pan_x, pan_y)
+    pub(crate) fn camera_target(&self) -> (f64, f64, f64) {
+        match &self.camera_animation {
+            Some(anim) => (anim.to_zoom, anim.to_pan_x, anim.to_pan_y),
+            None => (self.zoom, self.pan_x, self.pan_y),
+        }
+    }
+
+    /// 카메라 이동 (애니메이션 설정에 따라 부드럽게 또는 즉시)
+    pub(crate) fn set_camera(&mut self, zoom: f64, pan_x: f64, pan_y: f64) {
+        if !self.camera_animated || self.camera_duration_ms <= 0.0 {
+            self.jump_camera(zoom, pan_x, pan_y);
+            return;
+        }
+
+        self.camera_animation = Some(CameraAnimation {
+            from_zoom: self.zoom,
+            from_center_x: self.screen_to_world_x(self.logical_width / 2.0),
+            from_center_y: self.screen_to_world_y(self.logical_height / 2.0),
+            to_zoom: zoom,
+            to_pan_x: pan_x,
+            to_pan_y: pan_y,
+            start_time: None,
+            duration_ms: self.camera_duration_ms,
+        });
+        self.needs_render = true;
+    }
+
+    /// 카메라 즉시 이동 (진행 중인 애니메이션 취소)
+    pub(crate) fn jump_camera(&mut self, zoom: f64, pan_x: f64, pan_y: f64) {
+        self.camera_animation = None;
+        self.zoom = zoom;
+        self.pan_x = pan_x;
+        self.pan_y = pan_y;
+        self.needs_render = true;
+    }
+
+    /// 진행 중인 애니메이션을 현재 위치에서 멈춤 (직접 조작 시작 시)
+    pub(crate) fn stop_camera_animation(&mut self) {
+        self.camera_animation = None;
+    }
+
+    /// 애니메이션 한 프레임 진행 (rAF 루프에서 호출)
+    pub(crate) fn tick_camera(&mut self, now: f64) {
+        let half_w = self.logical_width / 2.0;
+        let half_h = self.logical_height / 2.0;
+
+        let Some(anim) = self.camera_animation.as_mut() else {
+            return;
+        };
+        let start = *anim.start_time.get_or_insert(now);
+        let t = ((now - start) / anim.duration_ms).clamp(0.0, 1.0);
+
+        if t >= 1.0 {
+            let (zoom, pan_x, pan_y) = (anim.to_zoom, anim.to_pan_x, anim.to_pan_y);
+            self.jump_camera(zoom, pan_x, pan_y);
+            return;
+        }
+
+        // 줌은 로그 공간, 화면 중심은 월드 공간에서 보간 (확대/축소 속도가 균일)
+        let e = ease_in_out(t);
+        let to_center_x = (half_w - anim.to_pan_x) / anim.to_zoom;
+        let to_center_y = (half_h - anim.to_pan_y) / anim.to_zoom;
+        let zoom = (anim.from_zoom.ln() + (anim.to_zoom.ln() - anim.from_zoom.ln()) * e).exp();
+        let center_x = anim.from_center_x + (to_center_x - anim.from_center_x) * e;
+        let center_y = anim.from_center_y + (to_center_y - anim.from_center_y) * e;
+
+        self.zoom = zoom;
+        self.pan_x = half_w - center_x * zoom;
+        self.pan_y = half_h - center_y * zoom;
+        self.needs_render = true;
+    }
+
+    /// 카메라 애니메이션 사용 여부 (테스트/접근성용으로 끌 수 있음)
+    pub(crate) fn set_camera_animation(&mut self, enabled: bool, duration_ms: f64) {
+        self.camera_animated = enabled;
+        self.camera_duration_ms = duration_ms.max(0.0);
+        if !enabled {
+            if let Some((zoom, pan_x, pan_y)) = self
+                .camera_animation
+                .as_ref()
+                .map(|a| (a.to_zoom, a.to_pan_x, a.to_pan_y))
+            {
+                self.jump_camera(zoom, pan_x, pan_y);
+            }
+        }
+    }
+
+    /// 월드 영역이 화면에 꽉 차도록 하는 카메라 (zoom, pan_x, pan_y)
+    pub(crate) fn camera_for_bounds(&self, bb: &BoundingBox) -> (f64, f64, f64) {
+        let content_w = (bb.max_x - bb.min_x).max(1.0);
+        let content_h = (bb.max_y - bb.min_y).max(1.0);
+
+        let scale_x = (self.logical_width - FIT_PADDING * 2.0) / content_w;
+        let scale_y = (self.logical_height - FIT_PADDING * 2.0) / content_h;
+        let zoom = self.camera_config.clamp_zoom(scale_x.min(scale_y));
+
+        let center_x = (bb.min_x + bb.max_x) / 2.0;
+        let center_y = (bb.min_y + bb.max_y) / 2.0;
+
+        (
+            zoom,
+            self.logical_width / 2.0 - center_x * zoom,
+            self.logical_height / 2.0 - center_y * zoom,
+        )
+    }
+
+    /// 선택 영역으로 줌. 선택이 없으면 false
+    pub(crate) fn zoom_to_selection(&mut self) -> bool {
+        let bounds = combined_bounds(
+            self.scope()
+                .iter()
+                .filter(|e| self.selected_ids.contains(&e.id)),
+        );
+        let Some(bb) = bounds else {
+            return false;
+        };
+        let (zoom, pan_x, pan_y) = self.camera_for_bounds(&bb);
+        self.set_camera(zoom, pan_x, pan_y);
+        true
+    }
+
+    /// 특정 요소로 줌. 요소가 없으면 false
+    pub(crate) fn zoom_to_element(&mut self, id: u32) -> bool {
+        let Some(bb) = find_element(&self.elements, id).and_then(|e| e.bounding_box()) else {
+            return false;
+        };
+        let (zoom, pan_x, pan_y) = self.camera_for_bounds(&bb);
+        self.set_camera(zoom, pan_x, pan_y);
+        true
+    }
+
+    // ===== 뷰 북마크 =====
+
+    /// 현재 카메라(전환 중이면 목표)를 이름으로 저장
+    pub(crate) fn save_bookmark(&mut self, name: &str) {
+        let (zoom, pan_x, pan_y) = self.camera_target();
+        let bookmark = Bookmark {
+            name: name.to_string(),
+            center_x: (self.logical_width / 2.0 - pan_x) / zoom,
+            center_y: (self.logical_height / 2.0 - pan_y) / zoom,
+            zoom,
+        };
+        upsert_bookmark(&mut self.bookmarks, bookmark.clone());
+        self.log_op(LogOp::SetBookmark { bookmark });
+    }
+
+    /// 북마크 삭제. 없으면 false
+    pub(crate) fn delete_bookmark(&mut self, name: &str) -> bool {
+        if !remove_bookmark(&mut self.bookmarks, name) {
+            return false;
+        }
+        self.bookmark_index = None;
+        self.log_op(LogOp::RemoveBookmark {
+            name: name.to_string(),
+        });
+        true
+    }
+
+    /// 북마크 위치로 이동. 없으면 false
+    pub(crate) fn go_to_bookmark(&mut self, name: &str) -> bool {
+        match self.bookmarks.iter().position(|b| b.name == name) {
+            Some(index) => {
+                self.go_to_bookmark_index(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 저장 순서상 다음(또는 이전) 북마크로 이동 (프레젠테이션). 이동한 북마크 이름 반환
+    pub(crate) fn step_bookmark(&mut self, forward: bool) -> Option<String> {
+        let len = self.bookmarks.len();
+        if len == 0 {
+            return None;
+        }
+        let index = match (self.bookmark_index, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+        };
+        self.go_to_bookmark_index(index);
+        Some(self.bookmarks[index].name.clone())
+    }
+
+    fn go_to_bookmark_index(&mut self, index: usize) {
+        let b = &self.bookmarks[index];
+        let zoom = self.camera_config.clamp_zoom(b.zoom);
+        let pan_x = self.logical_width / 2.0 - b.center_x * zoom;
+        let pan_y = self.logical_height / 2.0 - b.center_y * zoom;
+        self.bookmark_index = Some(index);
+        self.set_camera(zoom, pan_x, pan_y);
+    }
+
+    /// 북마크 목록 JSON
+    pub(crate) fn get_bookmarks(&self) -> String {
+        serde_json::to_string(&self.bookmarks).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    // ===== 카메라 설정 =====
+
+    /// 카메라 설정 적용 (JSON, 생략한 필드는 기본값). 현재 줌도 새 범위로 맞춤
+    pub(crate) fn set_camera_config(&mut self, json: &str) -> Result<(), String> {
+        let config: CameraConfig = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        self.camera_config = config.validate()?;
+
+        let (zoom, _, _) = self.camera_target();
+        let clamped = self.camera_config.clamp_zoom(zoom);
+        if clamped != zoom {
+            self.set_zoom(clamped);
+        }
+        Ok(())
+    }
+
+    /// 현재 카메라 설정 JSON
+    pub(crate) fn get_camera_config(&self) -> String {
+        serde_json::to_string(&self.camera_config).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// 다음 줌 단계로 (캔버스 중심 기준)
+    pub(crate) fn zoom_step(&mut self, up: bool) {
+        let (zoom, _, _) = self.camera_target();
+        let next = self.camera_config.step_zoom(zoom, up);
+        self.set_zoom(next);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+    use crate::CanvasInner;
+
+    fn rect(id: u32, x: f64, y: f64, w: f64, h: f64) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect { x, y, w, h },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    /// 카메라(전환 중이면 목표)의 화면 중심 월드 좌표
+    fn target_center(c: &CanvasInner) -> (f64, f64) {
+        let (zoom, pan_x, pan_y) = c.camera_target();
+        (
+            (c.logical_width / 2.0 - pan_x) / zoom,
+            (c.logical_height / 2.0 - pan_y) / zoom,
+        )
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn tick_camera_interpolates_to_target() {
+        let mut c = CanvasInner::for_test();
+        let center = (c.screen_to_world_x(400.0), c.screen_to_world_y(300.0));
+        c.set_zoom(4.0);
+        assert_eq!(c.zoom, 1.0);
+        assert_eq!(c.camera_target().0, 4.0);
+
+        // 첫 프레임이 시작 시각
+        c.tick_camera(1000.0);
+        assert_close(c.zoom, 1.0);
+
+        // 줌은 로그 공간에서 보간, 중간 지점 ease 값은 0.5
+        c.tick_camera(1150.0);
+        assert_close(c.zoom, 2.0);
+        assert_close(c.screen_to_world_x(400.0), center.0);
+        assert_close(c.screen_to_world_y(300.0), center.1);
+
+        c.tick_camera(1300.0);
+        assert_eq!(c.zoom, 4.0);
+        assert!(c.camera_animation.is_none());
+        assert_eq!(c.camera_target(), (c.zoom, c.pan_x, c.pan_y));
+    }
+
+    #[test]
+    fn zoom_steps_compound_from_target() {
+        let mut c = CanvasInner::for_test();
+        c.zoom_step(true);
+        c.zoom_step(true);
+        c.tick_camera(0.0);
+        c.tick_camera(100.0);
+        c.zoom_step(true);
+        assert_eq!(c.camera_target().0, 8.0);
+
+        // 애니메이션을 끄면 목표로 즉시 이동
+        c.set_camera_animation(false, 0.0);
+        assert_eq!(c.zoom, 8.0);
+        c.zoom_step(false);
+        assert_eq!(c.zoom, 4.0);
+    }
+
+    #[test]
+    fn zoom_to_selection_frames_selected_elements() {
+        let mut c = CanvasInner::for_test();
+        c.elements = vec![
+            rect(1, 0.0, 0.0, 10.0, 10.0),
+            rect(2, 100.0, 50.0, 100.0, 50.0),
+        ];
+        assert!(!c.zoom_to_selection());
+
+        c.selected_ids.insert(2);
+        assert!(c.zoom_to_selection());
+        let (cx, cy) = target_center(&c);
+        assert_close(cx, 150.0);
+        assert_close(cy, 75.0);
+        // 선 두께를 포함한 101×51 경계 상자: 가로 (800 - 2*40) / 101 이 더 작음
+        assert_close(c.camera_target().0, 720.0 / 101.0);
+    }
+
+    #[test]
+    fn zoom_to_element_finds_grouped_children_and_clamps() {
+        let mut c = CanvasInner::for_test();
+        let child = rect(3, 20.0, 40.0, 0.5, 0.5);
+        let mut group = rect(2, 0.0, 0.0, 0.0, 0.0);
+        group.shape = Shape::Group {
+            children: vec![child],
+        };
+        c.elements = vec![rect(1, 0.0, 0.0, 10.0, 10.0), group];
+
+        assert!(!c.zoom_to_element(99));
+        assert!(c.zoom_to_element(3));
+        let (cx, cy) = target_center(&c);
+        assert_close(cx, 20.25);
+        assert_close(cy, 40.25);
+        assert_eq!(c.camera_target().0, 10.0);
+    }
+
+    #[test]
+    fn reset_view_respects_zoom_limits() {
+        let mut c = CanvasInner::for_test();
+        c.set_camera_config(r#"{"min_zoom": 2.0, "max_zoom": 8.0}"#)
+            .unwrap();
+        c.reset_view();
+        assert_eq!(c.camera_target(), (2.0, 0.0, 0.0));
+
+        c.set_camera_config(r#"{"min_zoom": 0.1, "max_zoom": 0.5}"#)
+            .unwrap();
+        c.reset_view();
+        assert_eq!(c.camera_target().0, 0.5);
+    }
+
+    #[test]
+    fn fit_empty_document_respects_zoom_limits() {
+        let mut c = CanvasInner::for_test();
+        c.set_camera_config(r#"{"min_zoom": 3.0, "max_zoom": 5.0}"#)
+            .unwrap();
+        c.fit_to_view();
+        assert_eq!(c.camera_target().0, 3.0);
+    }
+}
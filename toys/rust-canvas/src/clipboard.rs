@@ -0,0 +1,322 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{combined_bounds, union_bounds, Element, Shape};
+use crate::svg_import::parse_svg;
+use crate::CanvasInner;
+
+// ===== 시스템 클립보드 교환 (JSON / SVG) =====
+
+/// 클립보드 JSON 페이로드의 MIME 타입 (웹 커스텀 포맷은 "web " 접두사를 붙여 사용)
+pub(crate) const CLIPBOARD_MIME: &str = "application/x-rust-canvas+json";
+
+/// 페이로드 식별자
+const PAYLOAD_FORMAT: &str = "rust-canvas/elements";
+const PAYLOAD_VERSION: u32 = 1;
+
+/// 클립보드 JSON 페이로드
+#[derive(Serialize, Deserialize)]
+struct ClipboardPayload {
+    format: String,
+    version: u32,
+    elements: Vec<Element>,
+}
+
+impl CanvasInner {
+    /// 현재 범위의 선택 요소 (z-order 순)
+    fn selected_elements(&self) -> Vec<Element> {
+        self.scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .cloned()
+            .collect()
+    }
+
+    /// 선택 요소를 클립보드 JSON으로 (선택이 없으면 None)
+    pub(crate) fn selection_to_json(&self) -> Option<String> {
+        let elements = self.selected_elements();
+        if elements.is_empty() {
+            return None;
+        }
+        serde_json::to_string(&ClipboardPayload {
+            format: PAYLOAD_FORMAT.to_string(),
+            version: PAYLOAD_VERSION,
+            elements,
+        })
+        .ok()
+    }
+
+    /// 선택 요소만 담은 독립 SVG (다른 앱 붙여넣기용, 선택이 없으면 None)
+    pub(crate) fn selection_to_svg(&self) -> Option<String> {
+        let elements = self.selected_elements();
+        let bb = combined_bounds(&elements)?;
+
+        let padding = 10.0;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            bb.min_x - padding,
+            bb.min_y - padding,
+            (bb.max_x - bb.min_x) + padding * 2.0,
+            (bb.max_y - bb.min_y) + padding * 2.0
+        );
+        svg.push('\n');
+        for elem in &elements {
+            let s = elem.to_svg();
+            if !s.is_empty() {
+                svg.push_str("  ");
+                svg.push_str(&s);
+                svg.push('\n');
+            }
+        }
+        svg.push_str("</svg>");
+        Some(svg)
+    }
+
+    /// 붙여넣을 위치 (월드): 커서가 캔버스 위에 있으면 커서, 아니면 뷰포트 중앙
+    pub(crate) fn paste_anchor(&self) -> (f64, f64) {
+        let on_canvas = self.cursor_x >= 0.0
+            && self.cursor_y >= 0.0
+            && self.cursor_x <= self.logical_width
+            && self.cursor_y <= self.logical_height;
+        if self.cursor_inside && on_canvas {
+            return (
+                self.screen_to_world_x(self.cursor_x),
+                self.screen_to_world_y(self.cursor_y),
+            );
+        }
+        let view = self.visible_world_bounds();
+        (
+            (view.min_x + view.max_x) / 2.0,
+            (view.min_y + view.max_y) / 2.0,
+        )
+    }
+
+    /// 요소들을 중심이 붙여넣기 위치에 오도록 추가하고 선택 → 추가한 개수.
+    /// 그리드 스냅 중이면 좌상단을 그리드에 맞춤 (영역이 없는 요소는 건너뜀)
+    pub(crate) fn paste_at_anchor(&mut self, elements: &[Element]) -> usize {
+        let elements: Vec<Element> = elements
+            .iter()
+            .filter(|e| e.geometry_bounds().is_some())
+            .cloned()
+            .collect();
+        let Some(bb) = union_bounds(elements.iter().filter_map(|e| e.geometry_bounds())) else {
+            return 0;
+        };
+        let (ax, ay) = self.paste_anchor();
+        let (min_x, min_y) = self.snap_to_grid(
+            ax - (bb.max_x - bb.min_x) / 2.0,
+            ay - (bb.max_y - bb.min_y) / 2.0,
+        );
+        self.insert_copies(&elements, min_x - bb.min_x, min_y - bb.min_y);
+        elements.len()
+    }
+
+    /// 시스템 클립보드 데이터 붙여넣기 (클립보드 JSON 또는 SVG 텍스트) → 추가한 개수
+    pub(crate) fn paste_clipboard_data(&mut self, data: &str) -> Result<usize, String> {
+        let data = data.trim();
+        let elements = if data.starts_with('{') {
+            let payload: ClipboardPayload =
+                serde_json::from_str(data).map_err(|e| e.to_string())?;
+            if payload.format != PAYLOAD_FORMAT {
+                return Err(format!("unknown clipboard format: {}", payload.format));
+            }
+            if payload.version > PAYLOAD_VERSION {
+                return Err(format!(
+                    "unsupported clipboard version: {}",
+                    payload.version
+                ));
+            }
+            payload.elements
+        } else if data.starts_with('<') {
+            parse_svg(data)?
+        } else {
+            return Err("unsupported clipboard data".to_string());
+        };
+
+        // 잠금/숨김 상태는 가져오지 않음 (붙여넣은 요소는 바로 선택됨)
+        let elements: Vec<Element> = elements
+            .into_iter()
+            .map(|mut e| {
+                clear_flags(&mut e);
+                e
+            })
+            .collect();
+        Ok(self.paste_at_anchor(&elements))
+    }
+}
+
+/// 요소와 모든 하위 요소의 잠금/숨김 해제
+fn clear_flags(elem: &mut Element) {
+    elem.locked = false;
+    elem.hidden = false;
+    if let Shape::Group { children } = &mut elem.shape {
+        children.iter_mut().for_each(clear_flags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Style;
+
+    fn rect(id: u32, x: f64, y: f64) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect {
+                x,
+                y,
+                w: 20.0,
+                h: 20.0,
+            },
+            style: Style {
+                color: "#123456".to_string(),
+                width: 3.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    /// 잠긴/숨긴 자식을 가진 그룹 1과 사각형 4를 선택한 캔버스
+    fn canvas() -> CanvasInner {
+        let mut hidden = rect(3, 40.0, 0.0);
+        hidden.hidden = true;
+        hidden.locked = true;
+        let mut group = rect(1, 0.0, 0.0);
+        group.shape = Shape::Group {
+            children: vec![rect(2, 0.0, 0.0), hidden],
+        };
+        let mut c = CanvasInner::for_test();
+        c.elements = vec![group, rect(4, 0.0, 40.0)];
+        c.next_id = 5;
+        c.selected_ids = [1, 4].into();
+        c
+    }
+
+    /// 요소 트리의 (잠금, 숨김) 플래그
+    fn flags(elements: &[Element]) -> Vec<(bool, bool)> {
+        elements
+            .iter()
+            .flat_map(|e| {
+                let mut v = vec![(e.locked, e.hidden)];
+                if let Shape::Group { children } = &e.shape {
+                    v.extend(flags(children));
+                }
+                v
+            })
+            .collect()
+    }
+
+    #[test]
+    fn json_round_trip_pastes_unlocked_visible_copies() {
+        let source = canvas();
+        let json = source.selection_to_json().unwrap();
+
+        let mut c = CanvasInner::for_test();
+        c.next_id = 10;
+        assert_eq!(c.paste_clipboard_data(&json), Ok(2));
+        assert_eq!(c.elements.len(), 2);
+        assert_eq!(c.selected_ids, [10, 13].into());
+        assert!(flags(&c.elements).iter().all(|f| *f == (false, false)));
+
+        // 모양과 스타일은 그대로, 위치만 이동
+        let mut pasted = c.elements.clone();
+        let bb = combined_bounds(&pasted).unwrap();
+        let orig_bb = combined_bounds(&source.elements).unwrap();
+        let mut next_id = 1;
+        for e in &mut pasted {
+            e.translate(orig_bb.min_x - bb.min_x, orig_bb.min_y - bb.min_y);
+            e.reassign_ids(&mut next_id);
+        }
+        let mut expected = source.elements.clone();
+        expected.iter_mut().for_each(clear_flags);
+        assert!(pasted == expected);
+
+        // 선택이 없으면 내보내지 않음
+        c.selected_ids.clear();
+        assert!(c.selection_to_json().is_none());
+    }
+
+    /// 붙여넣은 요소들의 영역 (선 두께 제외)
+    fn pasted_bounds(c: &CanvasInner) -> (f64, f64, f64, f64) {
+        let pasted: Vec<&Element> = c
+            .elements
+            .iter()
+            .filter(|e| c.selected_ids.contains(&e.id))
+            .collect();
+        let bb = union_bounds(pasted.iter().filter_map(|e| e.geometry_bounds())).unwrap();
+        (bb.min_x, bb.min_y, bb.max_x, bb.max_y)
+    }
+
+    /// 30×50 영역의 원본 두 개 (사각형 + 빈 그룹)
+    fn source() -> Vec<Element> {
+        let mut empty = rect(2, 0.0, 0.0);
+        empty.shape = Shape::Group { children: vec![] };
+        vec![rect(1, 0.0, 0.0), empty, rect(3, 10.0, 30.0)]
+    }
+
+    #[test]
+    fn pastes_centered_on_cursor_inside_canvas() {
+        let mut c = CanvasInner::for_test();
+        c.zoom = 2.0;
+        c.track_cursor(200.0, 100.0);
+        // 영역이 없는 빈 그룹은 세지도 추가하지도 않음
+        assert_eq!(c.paste_at_anchor(&source()), 2);
+        assert_eq!(c.elements.len(), 2);
+        assert_eq!(c.selected_ids.len(), 2);
+
+        let (cx, cy) = (c.screen_to_world_x(200.0), c.screen_to_world_y(100.0));
+        let (min_x, min_y, max_x, max_y) = pasted_bounds(&c);
+        assert!(((min_x + max_x) / 2.0 - cx).abs() < 1e-9);
+        assert!(((min_y + max_y) / 2.0 - cy).abs() < 1e-9);
+        assert!((max_x - min_x - 30.0).abs() < 1e-9 && (max_y - min_y - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn falls_back_to_viewport_center_after_cursor_leaves() {
+        let mut c = CanvasInner::for_test();
+        c.track_cursor(10.0, 10.0);
+        c.hide_cursor();
+        assert_eq!(c.paste_at_anchor(&source()), 2);
+
+        let (min_x, min_y, max_x, max_y) = pasted_bounds(&c);
+        assert!(((min_x + max_x) / 2.0 - 400.0).abs() < 1e-9);
+        assert!(((min_y + max_y) / 2.0 - 300.0).abs() < 1e-9);
+
+        // 캔버스 밖 좌표도 뷰포트 중앙으로
+        let mut c = CanvasInner::for_test();
+        c.track_cursor(-5.0, 10.0);
+        c.paste_at_anchor(&source());
+        let (min_x, _, max_x, _) = pasted_bounds(&c);
+        assert!(((min_x + max_x) / 2.0 - 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn snaps_top_left_to_grid() {
+        let mut c = CanvasInner::for_test();
+        c.grid.snap = true;
+        c.track_cursor(133.0, 247.0);
+        c.paste_at_anchor(&source());
+
+        // 중심 (133, 247) → 좌상단 (118, 222) → 그리드 (120, 220)
+        let (min_x, min_y, max_x, max_y) = pasted_bounds(&c);
+        assert_eq!((min_x, min_y), (120.0, 220.0));
+        assert_eq!((max_x, max_y), (150.0, 270.0));
+        assert_eq!(c.paste_at_anchor(&[]), 0);
+    }
+
+    #[test]
+    fn rejects_foreign_or_newer_payloads() {
+        let mut c = CanvasInner::for_test();
+        let foreign = r#"{"format":"other","version":1,"elements":[]}"#;
+        assert!(c.paste_clipboard_data(foreign).is_err());
+        let newer = format!(
+            r#"{{"format":"{}","version":{},"elements":[]}}"#,
+            PAYLOAD_FORMAT,
+            PAYLOAD_VERSION + 1
+        );
+        assert!(c.paste_clipboard_data(&newer).is_err());
+        assert!(c.paste_clipboard_data("hello").is_err());
+        assert!(c.elements.is_empty() && c.undo_stack.is_empty());
+    }
+}
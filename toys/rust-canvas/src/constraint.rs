@@ -0,0 +1,152 @@
+use crate::input::{MOD_ALT, MOD_SHIFT};
+use crate::models::Shape;
+use crate::{CanvasInner, ToolMode};
+
+// ===== 도형 그리기 제약 (Shift: 각도/정사각형, Alt: 기준점 전환) =====
+//
+// 기본 기준점: 선/사각형은 시작점이 한쪽 끝, 원은 시작점이 중심.
+// Alt는 이를 뒤집어 사각형은 중심에서, 원은 드래그 선분을 지름으로 그린다.
+
+/// 기본 각도 스냅 단위 (도)
+pub(crate) const DEFAULT_ANGLE_STEP_DEG: f64 = 15.0;
+
+/// (dx, dy)를 step 라디안 단위 각도로 스냅 (길이 유지)
+fn snap_angle(dx: f64, dy: f64, step: f64) -> (f64, f64) {
+    let len = (dx * dx + dy * dy).sqrt();
+    let angle = (dy.atan2(dx) / step).round() * step;
+    (len * angle.cos(), len * angle.sin())
+}
+
+/// (dx, dy)를 같은 길이의 정사각형 대각선으로 (긴 변 기준, 부호 유지)
+fn square(dx: f64, dy: f64) -> (f64, f64) {
+    let side = dx.abs().max(dy.abs());
+    (side.copysign(dx), side.copysign(dy))
+}
+
+impl CanvasInner {
+    /// 현재 시작점/끝점/수정자 키로 만든 도형 (프리뷰와 확정이 같은 결과를 쓰도록)
+    pub(crate) fn constrained_shape(&self) -> Option<Shape> {
+        let shift = self.shape_modifiers & MOD_SHIFT != 0;
+        let alt = self.shape_modifiers & MOD_ALT != 0;
+        let (sx, sy) = (self.shape_start_x, self.shape_start_y);
+        let (mut dx, mut dy) = (self.shape_end_x - sx, self.shape_end_y - sy);
+
+        match self.tool_mode {
+            ToolMode::Line => {
+                if shift {
+                    (dx, dy) = snap_angle(dx, dy, self.angle_step_deg.to_radians());
+                }
+                Some(Shape::Line {
+                    x1: sx,
+                    y1: sy,
+                    x2: sx + dx,
+                    y2: sy + dy,
+                })
+            }
+            ToolMode::Rectangle => {
+                if shift {
+                    (dx, dy) = square(dx, dy);
+                }
+                // Alt: 시작점이 중심, 드래그 벡터는 반쪽 크기
+                let (x0, y0, x1, y1) = if alt {
+                    (sx - dx, sy - dy, sx + dx, sy + dy)
+                } else {
+                    (sx, sy, sx + dx, sy + dy)
+                };
+                Some(Shape::Rect {
+                    x: x0.min(x1),
+                    y: y0.min(y1),
+                    w: (x1 - x0).abs(),
+                    h: (y1 - y0).abs(),
+                })
+            }
+            ToolMode::Circle => {
+                let dist = (dx * dx + dy * dy).sqrt();
+                // 기본: 시작점이 중심, Alt: 드래그 선분이 지름
+                if alt {
+                    Some(Shape::Circle {
+                        cx: sx + dx / 2.0,
+                        cy: sy + dy / 2.0,
+                        r: dist / 2.0,
+                    })
+                } else {
+                    Some(Shape::Circle {
+                        cx: sx,
+                        cy: sy,
+                        r: dist,
+                    })
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// 도형 그리기 중 수정자 키 변경 (포인터 이동 없이 키만 눌러도 프리뷰 갱신)
+    pub(crate) fn set_shape_modifiers(&mut self, modifiers: u32) {
+        if self.is_drawing_shape && self.shape_modifiers != modifiers {
+            self.shape_modifiers = modifiers;
+            self.needs_render = true;
+        }
+    }
+
+    /// Shift 각도 스냅 단위 설정 (도, 0 초과 90 이하)
+    pub(crate) fn set_angle_step(&mut self, degrees: f64) -> Result<(), String> {
+        if degrees.is_nan() || degrees <= 0.0 || degrees > 90.0 {
+            return Err("angle step must be in (0, 90] degrees".to_string());
+        }
+        self.angle_step_deg = degrees;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drag(tool: &str, to: (f64, f64), modifiers: u32) -> Shape {
+        let mut c = CanvasInner::for_test();
+        c.set_tool_mode(tool);
+        c.start_shape(100.0, 100.0);
+        c.update_shape(to.0, to.1, modifiers);
+        c.finish_shape(modifiers);
+        c.elements.pop().unwrap().shape
+    }
+
+    #[test]
+    fn circle_is_center_drag_by_default() {
+        let Shape::Circle { cx, cy, r } = drag("circle", (130.0, 140.0), 0) else {
+            panic!("not a circle");
+        };
+        assert_eq!((cx, cy, r), (100.0, 100.0, 50.0));
+    }
+
+    #[test]
+    fn alt_circle_uses_drag_as_diameter() {
+        let Shape::Circle { cx, cy, r } = drag("circle", (130.0, 140.0), MOD_ALT) else {
+            panic!("not a circle");
+        };
+        assert_eq!((cx, cy, r), (115.0, 120.0, 25.0));
+    }
+
+    #[test]
+    fn rect_constraints() {
+        let Shape::Rect { x, y, w, h } = drag("rect", (130.0, 110.0), MOD_SHIFT) else {
+            panic!("not a rect");
+        };
+        assert_eq!((x, y, w, h), (100.0, 100.0, 30.0, 30.0));
+
+        let Shape::Rect { x, y, w, h } = drag("rect", (130.0, 110.0), MOD_ALT) else {
+            panic!("not a rect");
+        };
+        assert_eq!((x, y, w, h), (70.0, 90.0, 60.0, 20.0));
+    }
+
+    #[test]
+    fn shift_line_snaps_to_angle_step() {
+        let Shape::Line { x2, y2, .. } = drag("line", (200.0, 104.0), MOD_SHIFT) else {
+            panic!("not a line");
+        };
+        assert!((y2 - 100.0).abs() < 1e-9);
+        assert!((x2 - (100.0 + 100.0_f64.hypot(4.0))).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,1422 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::layers::{Layer, DEFAULT_LAYER_ID};
+use crate::models::{Element, Point, Shape, Style};
+use crate::oplog::LogOp;
+use crate::CanvasInner;
+
+// ===== CRDT 문서 모델 (실시간 협업) =====
+//
+// 요소마다 shape/style/position/alive/layer/locked/hidden LWW 레지스터를 두고,
+// z-order는 position(분수 인덱스) + id 순으로 정렬한 시퀀스로 표현한다.
+// 레이어 목록도 같은 방식(속성/position/alive 레지스터)으로 복제한다.
+// 모든 연산은 Lamport 타임스탬프로 전순서가 정해지므로
+// 어떤 순서로 병합해도 모든 레플리카가 같은 상태로 수렴한다.
+
+/// 사이트 ID가 차지하는 요소 ID 상위 비트 시프트 (하위 24비트는 사이트별 카운터)
+pub(crate) const SITE_SHIFT: u32 = 24;
+
+/// 분수 인덱스 한 자리의 상한 (exclusive)
+const DIGIT_BASE: u32 = 1 << 16;
+
+/// 끝에 추가할 때 자리값 증가 폭 (깊이 증가 억제)
+const APPEND_STEP: u32 = 16;
+
+/// Lamport 타임스탬프 (counter 우선, 동률이면 site로 결정)
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Stamp {
+    pub(crate) counter: u64,
+    pub(crate) site: u32,
+}
+
+/// 정렬 위치 (분수 인덱스, 사전순 비교)
+pub(crate) type Position = Vec<u32>;
+
+/// Last-Writer-Wins 레지스터
+#[derive(Clone)]
+struct Lww<T> {
+    value: Option<T>,
+    stamp: Stamp,
+}
+
+impl<T: Clone> Lww<T> {
+    fn empty() -> Lww<T> {
+        Lww {
+            value: None,
+            stamp: Stamp::default(),
+        }
+    }
+
+    /// 더 최신 타임스탬프일 때만 값 갱신
+    fn set(&mut self, value: T, stamp: Stamp) -> bool {
+        if self.value.is_some() && stamp <= self.stamp {
+            return false;
+        }
+        self.value = Some(value);
+        self.stamp = stamp;
+        true
+    }
+}
+
+/// 레지스터 하나에 대한 갱신 내용
+#[derive(Clone)]
+pub(crate) enum Field {
+    Shape(Shape),
+    Style(Style),
+    Position(Position),
+    Alive(bool),
+    Layer(u32),
+    Locked(bool),
+    Hidden(bool),
+    /// 레이어 속성 (이름/표시/잠금/불투명도, id는 Op.id)
+    LayerInfo(Layer),
+    /// 레이어 목록 안의 위치
+    LayerPosition(Position),
+    LayerAlive(bool),
+}
+
+/// 업데이트 메시지의 단위 연산 (id는 필드에 따라 요소 ID 또는 레이어 ID)
+#[derive(Clone)]
+pub(crate) struct Op {
+    pub(crate) id: u32,
+    pub(crate) stamp: Stamp,
+    pub(crate) field: Field,
+}
+
+/// 요소 하나의 레지스터 묶음
+#[derive(Clone)]
+struct Entry {
+    shape: Lww<Shape>,
+    style: Lww<Style>,
+    position: Lww<Position>,
+    alive: Lww<bool>,
+    /// 레이어 (없으면 기본 레이어)
+    layer: Lww<u32>,
+    /// 잠금/숨김 (없으면 false)
+    locked: Lww<bool>,
+    hidden: Lww<bool>,
+}
+
+impl Entry {
+    fn empty() -> Entry {
+        Entry {
+            shape: Lww::empty(),
+            style: Lww::empty(),
+            position: Lww::empty(),
+            alive: Lww::empty(),
+            layer: Lww::empty(),
+            locked: Lww::empty(),
+            hidden: Lww::empty(),
+        }
+    }
+
+    /// 문서에 보이는 요소인지 (모든 필드가 도착했고 삭제되지 않음)
+    fn is_visible(&self) -> bool {
+        self.alive.value == Some(true)
+            && self.shape.value.is_some()
+            && self.style.value.is_some()
+            && self.position.value.is_some()
+    }
+}
+
+/// 레이어 하나의 레지스터 묶음
+#[derive(Clone)]
+struct LayerEntry {
+    info: Lww<Layer>,
+    position: Lww<Position>,
+    alive: Lww<bool>,
+}
+
+impl LayerEntry {
+    fn empty() -> LayerEntry {
+        LayerEntry {
+            info: Lww::empty(),
+            position: Lww::empty(),
+            alive: Lww::empty(),
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.alive.value == Some(true) && self.info.value.is_some() && self.position.value.is_some()
+    }
+}
+
+/// 레플리카 하나의 CRDT 문서
+pub(crate) struct Document {
+    site: u32,
+    clock: u64,
+    entries: HashMap<u32, Entry>,
+    layers: HashMap<u32, LayerEntry>,
+    /// 아직 내보내지 않은 로컬 연산
+    pending: Vec<Op>,
+}
+
+impl Document {
+    pub(crate) fn new(site: u32) -> Document {
+        Document {
+            site,
+            clock: 0,
+            entries: HashMap::new(),
+            layers: HashMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn tick(&mut self) -> Stamp {
+        self.clock += 1;
+        Stamp {
+            counter: self.clock,
+            site: self.site,
+        }
+    }
+
+    /// 로컬 연산 생성 → 적용 후 전송 대기열에 추가
+    fn local(&mut self, id: u32, field: Field) {
+        let op = Op {
+            id,
+            stamp: self.tick(),
+            field,
+        };
+        self.apply(&op);
+        self.pending.push(op);
+    }
+
+    /// 연산 적용 (멱등, 교환 가능). 상태가 바뀌었으면 true
+    pub(crate) fn apply(&mut self, op: &Op) -> bool {
+        self.clock = self.clock.max(op.stamp.counter);
+        let stamp = op.stamp;
+        match &op.field {
+            Field::Shape(shape) => self.entry(op.id).shape.set(shape.clone(), stamp),
+            Field::Style(style) => self.entry(op.id).style.set(style.clone(), stamp),
+            Field::Position(pos) => self.entry(op.id).position.set(pos.clone(), stamp),
+            Field::Alive(alive) => self.entry(op.id).alive.set(*alive, stamp),
+            Field::Layer(layer) => self.entry(op.id).layer.set(*layer, stamp),
+            Field::Locked(locked) => self.entry(op.id).locked.set(*locked, stamp),
+            Field::Hidden(hidden) => self.entry(op.id).hidden.set(*hidden, stamp),
+            Field::LayerInfo(layer) => {
+                let layer = Layer {
+                    id: op.id,
+                    ..layer.clone()
+                };
+                self.layer_entry(op.id).info.set(layer, stamp)
+            }
+            Field::LayerPosition(pos) => self.layer_entry(op.id).position.set(pos.clone(), stamp),
+            Field::LayerAlive(alive) => self.layer_entry(op.id).alive.set(*alive, stamp),
+        }
+    }
+
+    fn entry(&mut self, id: u32) -> &mut Entry {
+        self.entries.entry(id).or_insert_with(Entry::empty)
+    }
+
+    fn layer_entry(&mut self, id: u32) -> &mut LayerEntry {
+        self.layers.entry(id).or_insert_with(LayerEntry::empty)
+    }
+
+    /// 살아있는 레이어를 목록 순서 (position, id)로 구체화
+    pub(crate) fn layers(&self) -> Vec<Layer> {
+        let mut visible: Vec<(&Position, u32, &LayerEntry)> = self
+            .layers
+            .iter()
+            .filter(|(_, entry)| entry.is_visible())
+            .map(|(id, entry)| (entry.position.value.as_ref().unwrap(), *id, entry))
+            .collect();
+        visible.sort_by(|a, b| compare_order(a.0, a.1, b.0, b.1));
+        visible
+            .into_iter()
+            .map(|(_, _, entry)| entry.info.value.clone().unwrap())
+            .collect()
+    }
+
+    /// 보이는 요소를 z-order (position, id) 순으로 구체화
+    pub(crate) fn elements(&self) -> Vec<Element> {
+        let mut visible: Vec<(&Position, u32, &Entry)> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.is_visible())
+            .map(|(id, entry)| (entry.position.value.as_ref().unwrap(), *id, entry))
+            .collect();
+        visible.sort_by(|a, b| compare_order(a.0, a.1, b.0, b.1));
+        visible
+            .into_iter()
+            .map(|(_, id, entry)| Element {
+                id,
+                shape: entry.shape.value.clone().unwrap(),
+                style: entry.style.value.clone().unwrap(),
+                layer: entry.layer.value.unwrap_or_default(),
+                locked: entry.locked.value.unwrap_or_default(),
+                hidden: entry.hidden.value.unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// 로컬 요소/레이어 목록과 문서를 비교해 차이를 로컬 연산으로 기록
+    pub(crate) fn sync_from(&mut self, elements: &[Element], layers: &[Layer]) {
+        let present: HashSet<u32> = elements.iter().map(|e| e.id).collect();
+
+        // 1. 삭제된 요소
+        let mut removed: Vec<u32> = self
+            .entries
+            .iter()
+            .filter(|(id, entry)| entry.is_visible() && !present.contains(id))
+            .map(|(id, _)| *id)
+            .collect();
+        removed.sort_unstable();
+        for id in removed {
+            self.local(id, Field::Alive(false));
+        }
+
+        // 2. 추가/수정된 요소
+        for elem in elements {
+            let entry = self.entries.get(&elem.id);
+            let shape_changed = entry.and_then(|e| e.shape.value.as_ref()) != Some(&elem.shape);
+            let style_changed = entry.and_then(|e| e.style.value.as_ref()) != Some(&elem.style);
+            let revived = entry.and_then(|e| e.alive.value) != Some(true);
+            let layer_changed = entry.and_then(|e| e.layer.value).unwrap_or_default() != elem.layer;
+            let locked_changed =
+                entry.and_then(|e| e.locked.value).unwrap_or_default() != elem.locked;
+            let hidden_changed =
+                entry.and_then(|e| e.hidden.value).unwrap_or_default() != elem.hidden;
+            if shape_changed {
+                self.local(elem.id, Field::Shape(elem.shape.clone()));
+            }
+            if style_changed {
+                self.local(elem.id, Field::Style(elem.style.clone()));
+            }
+            if revived {
+                self.local(elem.id, Field::Alive(true));
+            }
+            if layer_changed {
+                self.local(elem.id, Field::Layer(elem.layer));
+            }
+            if locked_changed {
+                self.local(elem.id, Field::Locked(elem.locked));
+            }
+            if hidden_changed {
+                self.local(elem.id, Field::Hidden(elem.hidden));
+            }
+        }
+
+        // 3. z-order: 위치가 엄격히 증가하는 최장 부분열은 유지, 나머지만 새 위치 부여
+        //    (동시 추가로 같은 위치를 가진 요소는 하나만 유지해 이웃 사이에 항상 빈 자리 확보)
+        let positions: Vec<Option<Position>> = elements
+            .iter()
+            .map(|e| {
+                self.entries
+                    .get(&e.id)
+                    .and_then(|entry| entry.position.value.clone())
+            })
+            .collect();
+        for (i, pos) in reposition(&positions) {
+            self.local(elements[i].id, Field::Position(pos));
+        }
+
+        self.sync_layers(layers);
+    }
+
+    /// 레이어 목록 차이를 로컬 연산으로 기록 (요소와 같은 방식)
+    fn sync_layers(&mut self, layers: &[Layer]) {
+        let present: HashSet<u32> = layers.iter().map(|l| l.id).collect();
+        let mut removed: Vec<u32> = self
+            .layers
+            .iter()
+            .filter(|(id, entry)| entry.is_visible() && !present.contains(id))
+            .map(|(id, _)| *id)
+            .collect();
+        removed.sort_unstable();
+        for id in removed {
+            self.local(id, Field::LayerAlive(false));
+        }
+
+        for layer in layers {
+            let entry = self.layers.get(&layer.id);
+            let info_changed = entry.and_then(|e| e.info.value.as_ref()) != Some(layer);
+            let revived = entry.and_then(|e| e.alive.value) != Some(true);
+            if info_changed {
+                self.local(layer.id, Field::LayerInfo(layer.clone()));
+            }
+            if revived {
+                self.local(layer.id, Field::LayerAlive(true));
+            }
+        }
+
+        let positions: Vec<Option<Position>> = layers
+            .iter()
+            .map(|l| {
+                self.layers
+                    .get(&l.id)
+                    .and_then(|entry| entry.position.value.clone())
+            })
+            .collect();
+        for (i, pos) in reposition(&positions) {
+            self.local(layers[i].id, Field::LayerPosition(pos));
+        }
+    }
+
+    /// 대기 중인 로컬 연산 꺼내기
+    pub(crate) fn take_pending(&mut self) -> Vec<Op> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// 전체 상태를 연산 목록으로 (새 참여자 동기화용)
+    pub(crate) fn snapshot(&self) -> Vec<Op> {
+        let mut ids: Vec<&u32> = self.entries.keys().collect();
+        ids.sort_unstable();
+
+        let mut ops = Vec::new();
+        for id in ids {
+            let entry = &self.entries[id];
+            if let Some(v) = &entry.shape.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.shape.stamp,
+                    field: Field::Shape(v.clone()),
+                });
+            }
+            if let Some(v) = &entry.style.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.style.stamp,
+                    field: Field::Style(v.clone()),
+                });
+            }
+            if let Some(v) = &entry.position.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.position.stamp,
+                    field: Field::Position(v.clone()),
+                });
+            }
+            if let Some(v) = entry.alive.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.alive.stamp,
+                    field: Field::Alive(v),
+                });
+            }
+            if let Some(v) = entry.layer.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.layer.stamp,
+                    field: Field::Layer(v),
+                });
+            }
+            if let Some(v) = entry.locked.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.locked.stamp,
+                    field: Field::Locked(v),
+                });
+            }
+            if let Some(v) = entry.hidden.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.hidden.stamp,
+                    field: Field::Hidden(v),
+                });
+            }
+        }
+
+        let mut layer_ids: Vec<&u32> = self.layers.keys().collect();
+        layer_ids.sort_unstable();
+        for id in layer_ids {
+            let entry = &self.layers[id];
+            if let Some(v) = &entry.info.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.info.stamp,
+                    field: Field::LayerInfo(v.clone()),
+                });
+            }
+            if let Some(v) = &entry.position.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.position.stamp,
+                    field: Field::LayerPosition(v.clone()),
+                });
+            }
+            if let Some(v) = entry.alive.value {
+                ops.push(Op {
+                    id: *id,
+                    stamp: entry.alive.stamp,
+                    field: Field::LayerAlive(v),
+                });
+            }
+        }
+        ops
+    }
+}
+
+/// 요소와 모든 하위 요소의 ID
+fn collect_ids(elements: &[Element], out: &mut Vec<u32>) {
+    for elem in elements {
+        out.push(elem.id);
+        if let Shape::Group { children } = &elem.shape {
+            collect_ids(children, out);
+        }
+    }
+}
+
+/// (position, id) 전순서 비교
+fn compare_order(pa: &Position, ia: u32, pb: &Position, ib: u32) -> Ordering {
+    pa.cmp(pb).then(ia.cmp(&ib))
+}
+
+/// 목록 순서를 유지하는 데 필요한 새 위치 (인덱스, 위치).
+/// 위치가 엄격히 증가하는 최장 부분열은 그대로 두고 나머지는 이웃 사이에 배치
+fn reposition(positions: &[Option<Position>]) -> Vec<(usize, Position)> {
+    let keep = longest_increasing(positions);
+    let mut out = Vec::new();
+    let mut prev: Option<Position> = None;
+    for i in 0..positions.len() {
+        if keep.contains(&i) {
+            prev = positions[i].clone();
+            continue;
+        }
+        // 다음 유지 항목의 위치가 상한
+        let next = (i + 1..positions.len())
+            .find(|j| keep.contains(j))
+            .and_then(|j| positions[j].clone());
+        let pos = position_between(prev.as_deref(), next.as_deref());
+        out.push((i, pos.clone()));
+        prev = Some(pos);
+    }
+    out
+}
+
+/// 위치가 있는 항목들 중 position이 엄격히 증가하는 최장 부분열의 인덱스
+fn longest_increasing(positions: &[Option<Position>]) -> HashSet<usize> {
+    let candidates: Vec<usize> = (0..positions.len())
+        .filter(|&i| positions[i].is_some())
+        .collect();
+
+    // tails[k]: 길이 k+1 증가 부분열의 마지막 후보 인덱스
+    let mut tails: Vec<usize> = Vec::new();
+    let mut parent: Vec<Option<usize>> = vec![None; positions.len()];
+    for &i in &candidates {
+        let less = |j: &usize| positions[*j] < positions[i];
+        let k = tails.partition_point(less);
+        parent[i] = if k > 0 { Some(tails[k - 1]) } else { None };
+        if k == tails.len() {
+            tails.push(i);
+        } else {
+            tails[k] = i;
+        }
+    }
+
+    let mut keep = HashSet::new();
+    let mut cur = tails.last().copied();
+    while let Some(i) = cur {
+        keep.insert(i);
+        cur = parent[i];
+    }
+    keep
+}
+
+/// lo < 결과 < hi 를 만족하는 분수 인덱스 생성 (None은 무한대).
+/// 생성된 위치는 0으로 끝나지 않으므로 lo < hi 이면 항상 사이값이 존재함.
+/// lo >= hi 이면 사이값이 없으므로 hi를 무시하고 lo 뒤의 위치를 반환
+pub(crate) fn position_between(lo: Option<&[u32]>, hi: Option<&[u32]>) -> Position {
+    let lo = lo.unwrap_or(&[]);
+    let hi = hi.filter(|hi| lo < *hi);
+    let mut out = Vec::new();
+    let mut bounded = hi.is_some();
+    let mut i = 0;
+    loop {
+        let l = lo.get(i).copied().unwrap_or(0);
+        let h = if bounded {
+            hi.and_then(|h| h.get(i).copied()).unwrap_or(DIGIT_BASE)
+        } else {
+            DIGIT_BASE
+        };
+        if h > l + 1 {
+            let digit = if bounded {
+                l + (h - l) / 2
+            } else {
+                l + APPEND_STEP.min((h - l) / 2)
+            };
+            out.push(digit);
+            return out;
+        }
+        out.push(l);
+        if h > l {
+            // h == l + 1: 이 자리 이후로는 hi에 의해 제한되지 않음
+            bounded = false;
+        }
+        i += 1;
+    }
+}
+
+// ===== 업데이트 메시지 바이너리 인코딩 =====
+//
+// message := VERSION count:varint op*
+// op      := id:varint counter:varint site:varint tag:u8 payload
+// 정수는 LEB128 가변 길이, 실수는 f64 little-endian 8바이트,
+// 문자열은 길이(varint) + UTF-8 바이트.
+
+/// 메시지 형식 버전
+const WIRE_VERSION: u8 = 1;
+
+/// 그룹 중첩 허용 깊이 (잘못된 메시지의 무한 재귀 방지)
+const MAX_GROUP_DEPTH: usize = 64;
+
+const TAG_SHAPE: u8 = 0;
+const TAG_STYLE: u8 = 1;
+const TAG_POSITION: u8 = 2;
+const TAG_ALIVE: u8 = 3;
+const TAG_LAYER: u8 = 4;
+const TAG_LOCKED: u8 = 5;
+const TAG_HIDDEN: u8 = 6;
+const TAG_LAYER_INFO: u8 = 7;
+const TAG_LAYER_POSITION: u8 = 8;
+const TAG_LAYER_ALIVE: u8 = 9;
+
+const SHAPE_FREEHAND: u8 = 0;
+const SHAPE_LINE: u8 = 1;
+const SHAPE_RECT: u8 = 2;
+const SHAPE_CIRCLE: u8 = 3;
+const SHAPE_GROUP: u8 = 4;
+
+const FLAG_LOCKED: u8 = 1;
+const FLAG_HIDDEN: u8 = 2;
+
+struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn varint(&mut self, mut v: u64) {
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn str(&mut self, v: &str) {
+        self.varint(v.len() as u64);
+        self.buf.extend_from_slice(v.as_bytes());
+    }
+
+    fn position(&mut self, pos: &Position) {
+        self.varint(pos.len() as u64);
+        for digit in pos {
+            self.varint(*digit as u64);
+        }
+    }
+
+    fn style(&mut self, style: &Style) {
+        self.str(&style.color);
+        self.f64(style.width);
+    }
+
+    fn shape(&mut self, shape: &Shape) {
+        match shape {
+            Shape::Freehand { points } => {
+                self.u8(SHAPE_FREEHAND);
+                self.varint(points.len() as u64);
+                for p in points {
+                    self.f64(p.x);
+                    self.f64(p.y);
+                }
+            }
+            Shape::Line { x1, y1, x2, y2 } => {
+                self.u8(SHAPE_LINE);
+                for v in [x1, y1, x2, y2] {
+                    self.f64(*v);
+                }
+            }
+            Shape::Rect { x, y, w, h } => {
+                self.u8(SHAPE_RECT);
+                for v in [x, y, w, h] {
+                    self.f64(*v);
+                }
+            }
+            Shape::Circle { cx, cy, r } => {
+                self.u8(SHAPE_CIRCLE);
+                for v in [cx, cy, r] {
+                    self.f64(*v);
+                }
+            }
+            Shape::Group { children } => {
+                self.u8(SHAPE_GROUP);
+                self.varint(children.len() as u64);
+                for child in children {
+                    self.element(child);
+                }
+            }
+        }
+    }
+
+    fn element(&mut self, elem: &Element) {
+        self.varint(elem.id as u64);
+        self.shape(&elem.shape);
+        self.style(&elem.style);
+        self.varint(elem.layer as u64);
+        let mut flags = 0;
+        if elem.locked {
+            flags |= FLAG_LOCKED;
+        }
+        if elem.hidden {
+            flags |= FLAG_HIDDEN;
+        }
+        self.u8(flags);
+    }
+
+    fn op(&mut self, op: &Op) {
+        self.varint(op.id as u64);
+        self.varint(op.stamp.counter);
+        self.varint(op.stamp.site as u64);
+        match &op.field {
+            Field::Shape(shape) => {
+                self.u8(TAG_SHAPE);
+                self.shape(shape);
+            }
+            Field::Style(style) => {
+                self.u8(TAG_STYLE);
+                self.style(style);
+            }
+            Field::Position(pos) => {
+                self.u8(TAG_POSITION);
+                self.position(pos);
+            }
+            Field::Alive(v) => {
+                self.u8(TAG_ALIVE);
+                self.u8(*v as u8);
+            }
+            Field::Layer(v) => {
+                self.u8(TAG_LAYER);
+                self.varint(*v as u64);
+            }
+            Field::Locked(v) => {
+                self.u8(TAG_LOCKED);
+                self.u8(*v as u8);
+            }
+            Field::Hidden(v) => {
+                self.u8(TAG_HIDDEN);
+                self.u8(*v as u8);
+            }
+            Field::LayerInfo(layer) => {
+                self.u8(TAG_LAYER_INFO);
+                self.str(&layer.name);
+                self.u8(layer.visible as u8);
+                self.u8(layer.locked as u8);
+                self.f64(layer.opacity);
+            }
+            Field::LayerPosition(pos) => {
+                self.u8(TAG_LAYER_POSITION);
+                self.position(pos);
+            }
+            Field::LayerAlive(v) => {
+                self.u8(TAG_LAYER_ALIVE);
+                self.u8(*v as u8);
+            }
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| "truncated update message".to_string())?;
+        let out = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(out)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, String> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            v => Err(format!("invalid bool in update message: {}", v)),
+        }
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut v: u64 = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            v |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(v);
+            }
+        }
+        Err("varint too long in update message".to_string())
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        u32::try_from(self.varint()?)
+            .map_err(|_| "integer out of range in update message".to_string())
+    }
+
+    /// 요소 개수 (남은 바이트보다 많을 수 없음)
+    fn count(&mut self) -> Result<usize, String> {
+        let n = self.varint()?;
+        if n > (self.bytes.len() - self.pos) as u64 {
+            return Err("invalid length in update message".to_string());
+        }
+        Ok(n as usize)
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(raw))
+    }
+
+    fn str(&mut self) -> Result<String, String> {
+        let n = self.count()?;
+        String::from_utf8(self.take(n)?.to_vec()).map_err(|e| e.to_string())
+    }
+
+    fn position(&mut self) -> Result<Position, String> {
+        let n = self.count()?;
+        let mut pos = Vec::with_capacity(n);
+        for _ in 0..n {
+            pos.push(self.u32()?);
+        }
+        Ok(pos)
+    }
+
+    fn style(&mut self) -> Result<Style, String> {
+        Ok(Style {
+            color: self.str()?,
+            width: self.f64()?,
+        })
+    }
+
+    fn shape(&mut self, depth: usize) -> Result<Shape, String> {
+        Ok(match self.u8()? {
+            SHAPE_FREEHAND => {
+                let n = self.count()?;
+                let mut points = Vec::with_capacity(n);
+                for _ in 0..n {
+                    points.push(Point {
+                        x: self.f64()?,
+                        y: self.f64()?,
+                    });
+                }
+                Shape::Freehand { points }
+            }
+            SHAPE_LINE => Shape::Line {
+                x1: self.f64()?,
+                y1: self.f64()?,
+                x2: self.f64()?,
+                y2: self.f64()?,
+            },
+            SHAPE_RECT => Shape::Rect {
+                x: self.f64()?,
+                y: self.f64()?,
+                w: self.f64()?,
+                h: self.f64()?,
+            },
+            SHAPE_CIRCLE => Shape::Circle {
+                cx: self.f64()?,
+                cy: self.f64()?,
+                r: self.f64()?,
+            },
+            SHAPE_GROUP => {
+                if depth >= MAX_GROUP_DEPTH {
+                    return Err("groups nested too deeply in update message".to_string());
+                }
+                let n = self.count()?;
+                let mut children = Vec::with_capacity(n);
+                for _ in 0..n {
+                    children.push(self.element(depth + 1)?);
+                }
+                Shape::Group { children }
+            }
+            tag => return Err(format!("unknown shape tag in update message: {}", tag)),
+        })
+    }
+
+    fn element(&mut self, depth: usize) -> Result<Element, String> {
+        let id = self.u32()?;
+        let shape = self.shape(depth)?;
+        let style = self.style()?;
+        let layer = self.u32()?;
+        let flags = self.u8()?;
+        Ok(Element {
+            id,
+            shape,
+            style,
+            layer,
+            locked: flags & FLAG_LOCKED != 0,
+            hidden: flags & FLAG_HIDDEN != 0,
+        })
+    }
+
+    fn op(&mut self) -> Result<Op, String> {
+        let id = self.u32()?;
+        let stamp = Stamp {
+            counter: self.varint()?,
+            site: self.u32()?,
+        };
+        let field = match self.u8()? {
+            TAG_SHAPE => Field::Shape(self.shape(0)?),
+            TAG_STYLE => Field::Style(self.style()?),
+            TAG_POSITION => Field::Position(self.position()?),
+            TAG_ALIVE => Field::Alive(self.bool()?),
+            TAG_LAYER => Field::Layer(self.u32()?),
+            TAG_LOCKED => Field::Locked(self.bool()?),
+            TAG_HIDDEN => Field::Hidden(self.bool()?),
+            TAG_LAYER_INFO => Field::LayerInfo(Layer {
+                id,
+                name: self.str()?,
+                visible: self.bool()?,
+                locked: self.bool()?,
+                opacity: self.f64()?,
+            }),
+            TAG_LAYER_POSITION => Field::LayerPosition(self.position()?),
+            TAG_LAYER_ALIVE => Field::LayerAlive(self.bool()?),
+            tag => return Err(format!("unknown field tag in update message: {}", tag)),
+        };
+        Ok(Op { id, stamp, field })
+    }
+}
+
+/// 업데이트 메시지 인코딩 (바이너리)
+pub(crate) fn encode_ops(ops: &[Op]) -> Vec<u8> {
+    let mut w = Writer { buf: Vec::new() };
+    w.u8(WIRE_VERSION);
+    w.varint(ops.len() as u64);
+    for op in ops {
+        w.op(op);
+    }
+    w.buf
+}
+
+/// 업데이트 메시지 디코딩
+pub(crate) fn decode_ops(bytes: &[u8]) -> Result<Vec<Op>, String> {
+    let mut r = Reader { bytes, pos: 0 };
+    let version = r.u8()?;
+    if version != WIRE_VERSION {
+        return Err(format!("unsupported update message version: {}", version));
+    }
+    let n = r.count()?;
+    let mut ops = Vec::with_capacity(n);
+    for _ in 0..n {
+        ops.push(r.op()?);
+    }
+    if r.pos != bytes.len() {
+        return Err("trailing bytes in update message".to_string());
+    }
+    Ok(ops)
+}
+
+impl CanvasInner {
+    /// 협업 모드 시작. site는 1..=255, 이후 새 요소/레이어 ID 상위 8비트에 사이트가 들어감
+    pub(crate) fn enable_collaboration(&mut self, site: u32) -> Result<(), String> {
+        if site == 0 || site > 0xFF {
+            return Err("site id must be in 1..=255".to_string());
+        }
+
+        // 사이트 구간 밖의 기존 ID는 다른 레플리카의 ID와 충돌할 수 있음
+        // (undo/로그가 ID를 참조하므로 재부여하지 않고 거부)
+        let mut ids = Vec::new();
+        collect_ids(&self.elements, &mut ids);
+        let foreign_layer = self
+            .layers
+            .iter()
+            .any(|l| l.id != DEFAULT_LAYER_ID && l.id >> SITE_SHIFT == 0);
+        if foreign_layer || ids.iter().any(|id| id >> SITE_SHIFT == 0) {
+            return Err(
+                "collaboration must start from an empty document or one created with a site id"
+                    .to_string(),
+            );
+        }
+
+        let base = site << SITE_SHIFT;
+        let max_own = ids
+            .into_iter()
+            .filter(|id| id >> SITE_SHIFT == site)
+            .max()
+            .unwrap_or(base);
+        self.next_id = max_own + 1;
+
+        // 레이어 ID도 사이트 구간에서 발급 (기본 레이어는 모든 피어가 공유)
+        let max_own_layer = self
+            .layers
+            .iter()
+            .map(|l| l.id)
+            .filter(|id| id >> SITE_SHIFT == site)
+            .max()
+            .unwrap_or(base);
+        if self.next_layer_id >> SITE_SHIFT != site {
+            self.next_layer_id = base + 1;
+        }
+        self.reserve_layer_id(max_own_layer);
+
+        let mut doc = Document::new(site);
+        doc.sync_from(&self.elements, &self.layers);
+        self.collab = Some(doc);
+        Ok(())
+    }
+
+    /// 로컬 변경을 반영한 업데이트 메시지 생성 (변경 없으면 빈 벡터)
+    pub(crate) fn take_update(&mut self) -> Vec<u8> {
+        let Some(doc) = self.collab.as_mut() else {
+            return Vec::new();
+        };
+        doc.sync_from(&self.elements, &self.layers);
+        let ops = doc.take_pending();
+        if ops.is_empty() {
+            return Vec::new();
+        }
+        encode_ops(&ops)
+    }
+
+    /// 전체 문서 상태 인코딩 (새 참여자에게 전송)
+    pub(crate) fn encode_state(&mut self) -> Vec<u8> {
+        let Some(doc) = self.collab.as_mut() else {
+            return Vec::new();
+        };
+        doc.sync_from(&self.elements, &self.layers);
+        encode_ops(&doc.snapshot())
+    }
+
+    /// 원격 업데이트 병합 후 요소 목록 재구성
+    pub(crate) fn apply_update(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let ops = decode_ops(bytes)?;
+        let Some(doc) = self.collab.as_mut() else {
+            return Err("collaboration is not enabled".to_string());
+        };
+
+        // 아직 내보내지 않은 로컬 변경이 덮어써지지 않도록 먼저 기록
+        doc.sync_from(&self.elements, &self.layers);
+
+        let mut changed = false;
+        for op in &ops {
+            changed |= doc.apply(op);
+        }
+        if !changed {
+            return Ok(());
+        }
+
+        self.elements = doc.elements();
+        let layers = doc.layers();
+        if layers != self.layers {
+            self.restore_layers(layers);
+        }
+        // 타임랩스 재생이 실제로 본 문서를 재현하도록 병합 결과를 기록
+        self.log_op(LogOp::Merge {
+            elements: self.elements.clone(),
+            layers: self.layers.clone(),
+        });
+        // undo 항목의 인덱스/스냅샷은 병합 전 문서 기준이라 되돌리면 원격 변경까지
+        // 지우고 다른 피어에 전파됨 → 병합 시 undo/redo 기록을 버림
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_nudge = None;
+        self.last_duplicate = None;
+        let ids: HashSet<u32> = self.elements.iter().map(|e| e.id).collect();
+        self.selected_ids.retain(|id| ids.contains(id));
+        self.validate_scope();
+        self.needs_render = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CanvasInner의 협업 흐름(sync_from → 전송, 수신 → 재구성)을 흉내내는 레플리카
+    struct Replica {
+        doc: Document,
+        elements: Vec<Element>,
+        layers: Vec<Layer>,
+        next: u32,
+    }
+
+    impl Replica {
+        fn new(site: u32) -> Replica {
+            Replica {
+                doc: Document::new(site),
+                elements: Vec::new(),
+                layers: crate::layers::default_layers(),
+                next: (site << SITE_SHIFT) + 1,
+            }
+        }
+
+        fn add(&mut self, x: f64) -> u32 {
+            let id = self.next;
+            self.next += 1;
+            let elem = self.rect(id, x);
+            self.elements.push(elem);
+            id
+        }
+
+        fn rect(&self, id: u32, x: f64) -> Element {
+            Element {
+                id,
+                shape: Shape::Rect {
+                    x,
+                    y: 0.0,
+                    w: 10.0,
+                    h: 10.0,
+                },
+                style: Style {
+                    color: "#000000".to_string(),
+                    width: 2.0,
+                },
+                layer: 0,
+                locked: false,
+                hidden: false,
+            }
+        }
+
+        fn find(&mut self, id: u32) -> &mut Element {
+            self.elements.iter_mut().find(|e| e.id == id).unwrap()
+        }
+
+        /// 로컬 변경을 업데이트 메시지로 (take_update)
+        fn commit(&mut self) -> Vec<u8> {
+            self.doc.sync_from(&self.elements, &self.layers);
+            encode_ops(&self.doc.take_pending())
+        }
+
+        /// 원격 메시지 병합 (apply_update)
+        fn receive(&mut self, msg: &[u8]) {
+            self.doc.sync_from(&self.elements, &self.layers);
+            for op in decode_ops(msg).unwrap() {
+                self.doc.apply(&op);
+            }
+            self.elements = self.doc.elements();
+            self.layers = self.doc.layers();
+        }
+
+        fn order(&self) -> Vec<u32> {
+            self.elements.iter().map(|e| e.id).collect()
+        }
+    }
+
+    /// 결정적 셔플 (LCG)
+    fn shuffle<T>(items: &mut [T], seed: &mut u64) {
+        for i in (1..items.len()).rev() {
+            *seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let j = (*seed >> 33) as usize % (i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    /// 보낼 것이 없어질 때까지 서로 변경을 주고받음 → 걸린 라운드 수
+    fn settle(replicas: &mut [Replica]) -> usize {
+        for round in 0..8 {
+            let msgs: Vec<(usize, Vec<u8>)> = replicas
+                .iter_mut()
+                .enumerate()
+                .map(|(i, r)| (i, r.commit()))
+                .filter(|(_, m)| decode_ops(m).is_ok_and(|ops| !ops.is_empty()))
+                .collect();
+            if msgs.is_empty() {
+                return round;
+            }
+            for (from, msg) in &msgs {
+                for (i, r) in replicas.iter_mut().enumerate() {
+                    if i != *from {
+                        r.receive(msg);
+                    }
+                }
+            }
+        }
+        panic!("replicas did not settle");
+    }
+
+    fn assert_converged(replicas: &[Replica]) {
+        for r in &replicas[1..] {
+            assert_eq!(r.order(), replicas[0].order());
+            assert!(r.elements == replicas[0].elements);
+        }
+    }
+
+    #[test]
+    fn position_between_is_strictly_between() {
+        let cases: Vec<(Option<Position>, Option<Position>)> = vec![
+            (None, None),
+            (Some(vec![5]), None),
+            (None, Some(vec![5])),
+            (Some(vec![5]), Some(vec![6])),
+            (Some(vec![5]), Some(vec![5, 1])),
+            (Some(vec![5, 65535]), Some(vec![6])),
+            (Some(vec![1]), Some(vec![65535])),
+        ];
+        for (lo, hi) in cases {
+            let pos = position_between(lo.as_deref(), hi.as_deref());
+            if let Some(lo) = &lo {
+                assert!(*lo < pos, "{:?} !< {:?}", lo, pos);
+            }
+            if let Some(hi) = &hi {
+                assert!(pos < *hi, "{:?} !< {:?}", pos, hi);
+            }
+            assert_ne!(pos.last(), Some(&0));
+        }
+    }
+
+    #[test]
+    fn position_between_without_gap_goes_after_lo() {
+        let pos = position_between(Some(&[5]), Some(&[5]));
+        assert!(pos > vec![5]);
+    }
+
+    #[test]
+    fn concurrent_appends_converge_and_settle() {
+        let mut a = Replica::new(1);
+        let mut b = Replica::new(2);
+        a.add(0.0);
+        b.add(10.0);
+        let (ma, mb) = (a.commit(), b.commit());
+        a.receive(&mb);
+        b.receive(&ma);
+        assert_eq!(a.order(), b.order());
+
+        // 같은 위치를 가진 두 요소 사이에 끼워 넣어도 로컬 순서가 그대로 반영됨
+        let between = a.add(5.0);
+        let last = a.elements.pop().unwrap();
+        a.elements.insert(1, last);
+        let intended = a.order();
+        let mi = a.commit();
+        assert_eq!(
+            a.doc.elements().iter().map(|e| e.id).collect::<Vec<_>>(),
+            intended
+        );
+        b.receive(&mi);
+        assert_eq!(b.order(), intended);
+        assert_eq!(intended[1], between);
+
+        let mut replicas = [a, b];
+        settle(&mut replicas);
+        assert_converged(&replicas);
+
+        // 정착 후에는 더 이상 보낼 연산이 없음
+        for r in replicas.iter_mut() {
+            assert!(decode_ops(&r.commit()).unwrap().is_empty());
+        }
+
+        // 이후 동시 추가도 같은 순서로 수렴
+        let [mut a, mut b] = replicas;
+        let x = a.add(20.0);
+        let y = b.add(30.0);
+        let (ma, mb) = (a.commit(), b.commit());
+        a.receive(&mb);
+        b.receive(&ma);
+        let mut replicas = [a, b];
+        settle(&mut replicas);
+        assert_converged(&replicas);
+        let order = replicas[0].order();
+        assert_eq!(&order[order.len() - 2..], &[x.min(y), x.max(y)]);
+    }
+
+    #[test]
+    fn shuffled_delivery_converges() {
+        for seed in 1..=20u64 {
+            let mut seed = seed;
+            let mut replicas = [Replica::new(1), Replica::new(2), Replica::new(3)];
+
+            // 공통 시작 상태
+            let first = replicas[0].add(0.0);
+            let second = replicas[0].add(50.0);
+            let base = replicas[0].commit();
+            replicas[1].receive(&base);
+            replicas[2].receive(&base);
+
+            // 동시 편집 (레플리카마다 메시지 여러 개)
+            let mut msgs = Vec::new();
+            replicas[0].find(first).translate(5.0, 5.0);
+            msgs.push(replicas[0].commit());
+            replicas[0].add(100.0);
+            msgs.push(replicas[0].commit());
+
+            replicas[1].elements.retain(|e| e.id != second);
+            msgs.push(replicas[1].commit());
+            replicas[1].add(200.0);
+            replicas[1].find(first).style.color = "#ff0000".to_string();
+            msgs.push(replicas[1].commit());
+
+            replicas[2].elements.reverse();
+            msgs.push(replicas[2].commit());
+            replicas[2].find(second).hidden = true;
+            replicas[2].add(300.0);
+            msgs.push(replicas[2].commit());
+
+            // 각 레플리카가 모든 메시지(자기 것, 중복 포함)를 서로 다른 순서로 받음
+            for r in replicas.iter_mut() {
+                let mut inbox: Vec<&Vec<u8>> = msgs.iter().chain(msgs.iter().take(2)).collect();
+                shuffle(&mut inbox, &mut seed);
+                for msg in inbox {
+                    r.receive(msg);
+                }
+            }
+
+            settle(&mut replicas);
+            assert_converged(&replicas);
+            let ids = replicas[0].order();
+            assert_eq!(ids.len(), 4);
+            assert!(!ids.contains(&second));
+        }
+    }
+
+    fn canvas(site: u32) -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.enable_collaboration(site).unwrap();
+        c
+    }
+
+    /// 현재 ID로 사각형 추가 (로컬 편집 흉내)
+    fn draw(c: &mut CanvasInner, x: f64) -> u32 {
+        let id = c.next_id;
+        c.next_id += 1;
+        let elem = Element {
+            layer: c.active_layer,
+            ..Replica::new(0).rect(id, x)
+        };
+        c.elements.push(elem.clone());
+        c.push_action(crate::Action::AddElement { element: elem });
+        id
+    }
+
+    /// from의 로컬 변경을 to에 전달 (변경이 없으면 보내지 않음)
+    fn send(from: &mut CanvasInner, to: &mut CanvasInner) {
+        let msg = from.take_update();
+        if !msg.is_empty() {
+            to.apply_update(&msg).unwrap();
+        }
+    }
+
+    #[test]
+    fn undo_after_remote_merge_keeps_remote_elements() {
+        let mut a = canvas(1);
+        let mut b = canvas(2);
+        draw(&mut a, 0.0);
+        send(&mut a, &mut b);
+
+        a.clear();
+        send(&mut a, &mut b);
+        let remote = draw(&mut b, 10.0);
+        send(&mut b, &mut a);
+
+        // 병합 전 ClearAll 스냅샷으로 되돌리면 원격 요소가 사라짐
+        a.undo();
+        let ids: Vec<u32> = a.elements.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![remote]);
+        send(&mut a, &mut b);
+        assert_eq!(b.elements.len(), 1);
+        assert!(b.elements == a.elements);
+    }
+
+    fn layer_names(c: &CanvasInner) -> Vec<&str> {
+        c.layers.iter().map(|l| l.name.as_str()).collect()
+    }
+
+    #[test]
+    fn layers_are_replicated() {
+        let mut a = canvas(1);
+        let mut b = canvas(2);
+        let ink = a.add_layer("ink");
+        assert_eq!(ink >> SITE_SHIFT, 1);
+        let on_ink = draw(&mut a, 0.0);
+        send(&mut a, &mut b);
+        assert_eq!(layer_names(&b), vec!["Layer 1", "ink"]);
+        assert_eq!(b.elements[0].layer, ink);
+
+        // 피어의 레이어에 있는 요소는 맨 아래 레이어를 지워도 남음
+        assert!(b.remove_layer(DEFAULT_LAYER_ID));
+        let notes = b.add_layer("notes");
+        assert_eq!(notes >> SITE_SHIFT, 2);
+        b.move_layer(notes, 0);
+        send(&mut b, &mut a);
+        assert_eq!(layer_names(&a), vec!["notes", "ink"]);
+        assert_eq!(a.elements.len(), 1);
+        assert_eq!(a.elements[0].id, on_ink);
+        assert!(a.layers == b.layers);
+    }
+
+    #[test]
+    fn concurrent_layer_edits_converge() {
+        let mut a = canvas(1);
+        let mut b = canvas(2);
+        let ink = a.add_layer("ink");
+        let notes = a.add_layer("notes");
+        send(&mut a, &mut b);
+
+        a.rename_layer(ink, "pen");
+        a.move_layer(notes, 0);
+        b.remove_layer(ink);
+        b.set_layer_opacity(notes, 0.5);
+        let (ma, mb) = (a.take_update(), b.take_update());
+        a.apply_update(&mb).unwrap();
+        b.apply_update(&ma).unwrap();
+        send(&mut a, &mut b);
+        send(&mut b, &mut a);
+
+        assert!(a.layers == b.layers);
+        assert_eq!(layer_names(&a), vec!["notes", "Layer 1"]);
+        assert_eq!(a.layers[0].opacity, 0.5);
+    }
+
+    #[test]
+    fn encode_decode_round_trip() {
+        let mut r = Replica::new(3);
+        let id = r.add(1.5);
+        let child = r.elements[0].clone();
+        r.elements.push(Element {
+            id: id + 1,
+            shape: Shape::Group {
+                children: vec![child],
+            },
+            style: Style {
+                color: "#123456".to_string(),
+                width: 0.5,
+            },
+            layer: 7,
+            locked: true,
+            hidden: true,
+        });
+        r.layers.push(Layer {
+            id: 7,
+            name: "ink \u{1F58A}".to_string(),
+            visible: false,
+            locked: true,
+            opacity: 0.25,
+        });
+        r.doc.sync_from(&r.elements, &r.layers);
+        let ops = r.doc.snapshot();
+        let bytes = encode_ops(&ops);
+        let decoded = decode_ops(&bytes).unwrap();
+        assert_eq!(encode_ops(&decoded), bytes);
+
+        let mut other = Document::new(4);
+        for op in &decoded {
+            other.apply(op);
+        }
+        assert!(other.elements() == r.doc.elements());
+        assert!(other.layers() == r.layers);
+
+        assert!(decode_ops(&bytes[..bytes.len() - 1]).is_err());
+        assert!(decode_ops(&[WIRE_VERSION + 1, 0]).is_err());
+        assert!(decode_ops(b"[]").is_err());
+    }
+}
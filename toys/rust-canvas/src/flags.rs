@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::Element;
+use crate::{Action, CanvasInner};
+
+// ===== 요소 잠금 / 숨김 =====
+
+/// 요소별 상태 플래그
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ElementFlag {
+    /// 그려지지만 선택/이동 불가
+    Locked,
+    /// 그리지도, 내보내지도 않음
+    Hidden,
+}
+
+impl ElementFlag {
+    fn get(&self, elem: &Element) -> bool {
+        match self {
+            ElementFlag::Locked => elem.locked,
+            ElementFlag::Hidden => elem.hidden,
+        }
+    }
+}
+
+/// 요소 목록에서 ids의 플래그를 value로 설정
+pub(crate) fn set_flag(elements: &mut [Element], flag: ElementFlag, ids: &[u32], value: bool) {
+    for elem in elements.iter_mut().filter(|e| ids.contains(&e.id)) {
+        match flag {
+            ElementFlag::Locked => elem.locked = value,
+            ElementFlag::Hidden => elem.hidden = value,
+        }
+    }
+}
+
+impl CanvasInner {
+    /// 현재 범위의 요소 플래그 변경 (값이 바뀌는 요소만 기록, undo 가능).
+    /// 바뀐 요소가 있으면 true
+    fn set_flag_on(&mut self, flag: ElementFlag, ids: &[u32], value: bool) -> bool {
+        let ids: Vec<u32> = self
+            .scope()
+            .iter()
+            .filter(|e| ids.contains(&e.id) && flag.get(e) != value)
+            .map(|e| e.id)
+            .collect();
+        if ids.is_empty() {
+            return false;
+        }
+
+        let action = self.scoped(Action::SetFlag { flag, ids, value });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.drop_uninteractive_selection();
+        self.needs_render = true;
+        true
+    }
+
+    /// 현재 범위에서 플래그가 켜진 요소 ID
+    fn flagged_ids(&self, flag: ElementFlag) -> Vec<u32> {
+        self.scope()
+            .iter()
+            .filter(|e| flag.get(e))
+            .map(|e| e.id)
+            .collect()
+    }
+
+    /// 선택된 요소 잠금 (선택에서 빠짐)
+    pub(crate) fn lock_selected(&mut self) -> bool {
+        let ids: Vec<u32> = self.selected_ids.iter().copied().collect();
+        self.set_flag_on(ElementFlag::Locked, &ids, true)
+    }
+
+    /// 현재 범위의 잠긴 요소 모두 잠금 해제
+    pub(crate) fn unlock_all(&mut self) -> bool {
+        let ids = self.flagged_ids(ElementFlag::Locked);
+        self.set_flag_on(ElementFlag::Locked, &ids, false)
+    }
+
+    /// 선택된 요소 숨기기 (선택에서 빠짐)
+    pub(crate) fn hide_selected(&mut self) -> bool {
+        let ids: Vec<u32> = self.selected_ids.iter().copied().collect();
+        self.set_flag_on(ElementFlag::Hidden, &ids, true)
+    }
+
+    /// 현재 범위의 숨긴 요소 모두 표시
+    pub(crate) fn show_all(&mut self) -> bool {
+        let ids = self.flagged_ids(ElementFlag::Hidden);
+        self.set_flag_on(ElementFlag::Hidden, &ids, false)
+    }
+
+    /// 특정 요소의 잠금 설정 (현재 범위 내)
+    pub(crate) fn set_element_locked(&mut self, id: u32, locked: bool) -> bool {
+        self.set_flag_on(ElementFlag::Locked, &[id], locked)
+    }
+
+    /// 특정 요소의 숨김 설정 (현재 범위 내)
+    pub(crate) fn set_element_hidden(&mut self, id: u32, hidden: bool) -> bool {
+        self.set_flag_on(ElementFlag::Hidden, &[id], hidden)
+    }
+}
@@ -0,0 +1,221 @@
+use crate::models::union_bounds;
+use crate::{Action, CanvasInner};
+
+// ===== 뒤집기 =====
+
+impl CanvasInner {
+    /// 선택 요소를 선택 영역 중심 기준으로 뒤집기 (axis: "horizontal" | "vertical").
+    /// 선택이 없으면 false
+    pub(crate) fn flip_selected(&mut self, axis: &str) -> Result<bool, String> {
+        let horizontal = match axis {
+            "horizontal" => true,
+            "vertical" => false,
+            _ => return Err(format!("unknown flip axis: {}", axis)),
+        };
+
+        let selected: Vec<_> = self
+            .scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .collect();
+        let Some(bb) = union_bounds(selected.iter().filter_map(|e| e.geometry_bounds())) else {
+            return Ok(false);
+        };
+        let ids: Vec<u32> = selected.iter().map(|e| e.id).collect();
+        let center = if horizontal {
+            (bb.min_x + bb.max_x) / 2.0
+        } else {
+            (bb.min_y + bb.max_y) / 2.0
+        };
+
+        let action = self.scoped(Action::Flip {
+            ids,
+            horizontal,
+            center,
+        });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.needs_render = true;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Point, Shape, Style};
+    use crate::CanvasInner;
+
+    fn elem(id: u32, shape: Shape) -> Element {
+        Element {
+            id,
+            shape,
+            style: Style {
+                color: "#000000".to_string(),
+                width: 4.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn canvas(elements: Vec<Element>) -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.selected_ids = elements.iter().map(|e| e.id).collect();
+        c.next_id = elements.iter().map(|e| e.max_id()).max().unwrap_or(0) + 1;
+        c.elements = elements;
+        c
+    }
+
+    fn shapes(c: &CanvasInner) -> Vec<Shape> {
+        c.elements.iter().map(|e| e.shape.clone()).collect()
+    }
+
+    fn freehand(points: &[(f64, f64)]) -> Shape {
+        Shape::Freehand {
+            points: points.iter().map(|&(x, y)| Point { x, y }).collect(),
+        }
+    }
+
+    /// 선택 영역 x 0..60 (중심 30), y -10..30 (중심 10)
+    fn mixed() -> CanvasInner {
+        canvas(vec![
+            elem(1, freehand(&[(0.0, 0.0), (30.0, 10.0)])),
+            // 음수 너비/높이: x 30..50, y -10..0
+            elem(
+                2,
+                Shape::Rect {
+                    x: 50.0,
+                    y: 0.0,
+                    w: -20.0,
+                    h: -10.0,
+                },
+            ),
+            elem(
+                3,
+                Shape::Circle {
+                    cx: 50.0,
+                    cy: 20.0,
+                    r: 10.0,
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn flips_each_shape_about_selection_center() {
+        let mut c = mixed();
+        assert_eq!(c.flip_selected("horizontal"), Ok(true));
+        let expected = vec![
+            freehand(&[(60.0, 0.0), (30.0, 10.0)]),
+            Shape::Rect {
+                x: 30.0,
+                y: 0.0,
+                w: -20.0,
+                h: -10.0,
+            },
+            Shape::Circle {
+                cx: 10.0,
+                cy: 20.0,
+                r: 10.0,
+            },
+        ];
+        assert!(shapes(&c) == expected);
+
+        let mut c = mixed();
+        assert_eq!(c.flip_selected("vertical"), Ok(true));
+        let expected = vec![
+            freehand(&[(0.0, 20.0), (30.0, 10.0)]),
+            Shape::Rect {
+                x: 50.0,
+                y: 30.0,
+                w: -20.0,
+                h: -10.0,
+            },
+            Shape::Circle {
+                cx: 50.0,
+                cy: 0.0,
+                r: 10.0,
+            },
+        ];
+        assert!(shapes(&c) == expected);
+        // 뒤집어도 선택 영역은 그대로
+        let bb = c.elements[1].geometry_bounds().unwrap();
+        assert_eq!((bb.min_y, bb.max_y), (20.0, 30.0));
+    }
+
+    #[test]
+    fn flips_nested_groups_and_undoes() {
+        let inner = elem(
+            3,
+            Shape::Group {
+                children: vec![elem(
+                    4,
+                    Shape::Circle {
+                        cx: 30.0,
+                        cy: 5.0,
+                        r: 5.0,
+                    },
+                )],
+            },
+        );
+        let rect = elem(
+            2,
+            Shape::Rect {
+                x: 0.0,
+                y: 0.0,
+                w: 10.0,
+                h: 10.0,
+            },
+        );
+        let mut c = canvas(vec![elem(
+            1,
+            Shape::Group {
+                children: vec![rect, inner],
+            },
+        )]);
+        let original = c.elements.clone();
+
+        // x 0..35 → 중심 17.5
+        c.flip_selected("horizontal").unwrap();
+        let Shape::Group { children } = &c.elements[0].shape else {
+            panic!("group expected");
+        };
+        assert!(
+            children[0].shape
+                == Shape::Rect {
+                    x: 25.0,
+                    y: 0.0,
+                    w: 10.0,
+                    h: 10.0,
+                }
+        );
+        let Shape::Group { children: inner } = &children[1].shape else {
+            panic!("nested group expected");
+        };
+        assert!(
+            inner[0].shape
+                == Shape::Circle {
+                    cx: 5.0,
+                    cy: 5.0,
+                    r: 5.0,
+                }
+        );
+        let flipped = c.elements.clone();
+
+        assert_eq!(c.undo_stack.len(), 1);
+        c.undo();
+        assert!(c.elements == original);
+        c.redo();
+        assert!(c.elements == flipped);
+    }
+
+    #[test]
+    fn rejects_unknown_axis_and_empty_selection() {
+        let mut c = mixed();
+        assert!(c.flip_selected("diagonal").is_err());
+        c.selected_ids.clear();
+        assert_eq!(c.flip_selected("horizontal"), Ok(false));
+        assert!(c.undo_stack.is_empty());
+    }
+}
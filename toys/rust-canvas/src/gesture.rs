@@ -0,0 +1,224 @@
+use crate::input::Interaction;
+use crate::models::Point;
+use crate::CanvasInner;
+
+// ===== 멀티터치 제스처 (핀치 줌 / 두 손가락 팬) =====
+
+/// 활성 터치 포인터 (스크린 좌표)
+struct TouchPointer {
+    id: i32,
+    x: f64,
+    y: f64,
+}
+
+/// 핀치 시작 시점의 기준값
+struct Pinch {
+    start_distance: f64,
+    start_zoom: f64,
+    /// 시작 시 두 손가락 중심 아래의 월드 좌표 (계속 중심 아래에 고정)
+    anchor: Point,
+}
+
+/// 멀티터치 제스처 인식기
+#[derive(Default)]
+pub(crate) struct Gesture {
+    pointers: Vec<TouchPointer>,
+    pinch: Option<Pinch>,
+    /// 핀치 후 남은 손가락이 그리기를 시작하지 않도록 모든 손가락이 떨어질 때까지 잠금
+    locked: bool,
+}
+
+impl Gesture {
+    /// 첫 두 포인터의 중심과 거리
+    fn centroid_and_distance(&self) -> Option<(f64, f64, f64)> {
+        let (a, b) = (self.pointers.first()?, self.pointers.get(1)?);
+        let cx = (a.x + b.x) / 2.0;
+        let cy = (a.y + b.y) / 2.0;
+        let dist = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+        Some((cx, cy, dist))
+    }
+}
+
+impl CanvasInner {
+    /// 터치 포인터 다운. 제스처가 이벤트를 소비했으면 true
+    pub(crate) fn gesture_pointer_down(&mut self, id: i32, sx: f64, sy: f64) -> bool {
+        self.gesture.pointers.retain(|p| p.id != id);
+        self.gesture
+            .pointers
+            .push(TouchPointer { id, x: sx, y: sy });
+
+        match self.gesture.pointers.len() {
+            1 => self.gesture.locked,
+            2 => {
+                // 두 번째 손가락: 진행 중인 획/도형/이동을 취소하고 핀치 시작
+                self.cancel_interaction();
+                self.begin_pinch();
+                true
+            }
+            _ => true,
+        }
+    }
+
+    /// 터치 포인터 이동. 제스처가 이벤트를 소비했으면 true
+    pub(crate) fn gesture_pointer_move(&mut self, id: i32, sx: f64, sy: f64) -> bool {
+        let Some(p) = self.gesture.pointers.iter_mut().find(|p| p.id == id) else {
+            return self.gesture.locked;
+        };
+        p.x = sx;
+        p.y = sy;
+
+        if self.gesture.pinch.is_some() {
+            self.update_pinch();
+            return true;
+        }
+        self.gesture.locked
+    }
+
+    /// 터치 포인터 업/취소. 제스처가 이벤트를 소비했으면 true
+    pub(crate) fn gesture_pointer_up(&mut self, id: i32) -> bool {
+        let before = self.gesture.pointers.len();
+        self.gesture.pointers.retain(|p| p.id != id);
+        if self.gesture.pointers.len() == before {
+            return self.gesture.locked;
+        }
+
+        let consumed = self.gesture.pinch.is_some() || self.gesture.locked;
+        if self.gesture.pointers.len() < 2 && self.gesture.pinch.take().is_some() {
+            self.gesture.locked = true;
+            self.interaction = Interaction::Idle;
+        } else if self.gesture.pointers.len() >= 2 && self.gesture.pinch.is_some() {
+            // 세 손가락 이상에서 하나가 떨어지면 남은 두 손가락 기준으로 재시작
+            self.begin_pinch();
+        }
+        if self.gesture.pointers.is_empty() {
+            self.gesture.locked = false;
+        }
+        consumed
+    }
+
+    /// 현재 두 손가락 기준으로 핀치 기준값 설정
+    fn begin_pinch(&mut self) {
+        self.stop_camera_animation();
+        let Some((cx, cy, dist)) = self.gesture.centroid_and_distance() else {
+            return;
+        };
+        self.gesture.pinch = Some(Pinch {
+            start_distance: dist.max(1.0),
+            start_zoom: self.zoom,
+            anchor: Point {
+                x: self.screen_to_world_x(cx),
+                y: self.screen_to_world_y(cy),
+            },
+        });
+        self.interaction = Interaction::Pinching;
+    }
+
+    /// 핀치 배율로 줌, 중심 이동으로 팬 (앵커 월드 좌표가 손가락 중심을 따라감)
+    fn update_pinch(&mut self) {
+        let Some((cx, cy, dist)) = self.gesture.centroid_and_distance() else {
+            return;
+        };
+        let Some(pinch) = self.gesture.pinch.as_ref() else {
+            return;
+        };
+
+        let zoom = self
+            .camera_config
+            .clamp_zoom(pinch.start_zoom * dist / pinch.start_distance);
+        self.zoom = zoom;
+        self.pan_x = cx - pinch.anchor.x * zoom;
+        self.pan_y = cy - pinch.anchor.y * zoom;
+        self.needs_render = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch_down(c: &mut CanvasInner, id: i32, x: f64, y: f64) {
+        c.pointer_down(x, y, 1, 0, "touch", id);
+    }
+
+    fn touch_move(c: &mut CanvasInner, id: i32, x: f64, y: f64) {
+        c.pointer_move(x, y, 1, 0, "touch", id);
+    }
+
+    fn touch_up(c: &mut CanvasInner, id: i32, x: f64, y: f64) {
+        c.pointer_up(x, y, 0, 0, "touch", id);
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn pinch_scales_around_centroid() {
+        let mut c = CanvasInner::for_test();
+        touch_down(&mut c, 1, 300.0, 300.0);
+        touch_down(&mut c, 2, 500.0, 300.0);
+        assert!(c.interaction == Interaction::Pinching);
+
+        // 거리 200 → 400, 중심 (400, 300) → (500, 300)
+        touch_move(&mut c, 2, 700.0, 300.0);
+        assert_close(c.zoom, 2.0);
+        assert_close(c.screen_to_world_x(500.0), 400.0);
+        assert_close(c.screen_to_world_y(300.0), 300.0);
+
+        // 최대 줌으로 제한
+        touch_move(&mut c, 2, 100_000.0, 300.0);
+        assert_eq!(c.zoom, c.camera_config.max_zoom);
+    }
+
+    #[test]
+    fn two_finger_drag_pans_without_zoom() {
+        let mut c = CanvasInner::for_test();
+        touch_down(&mut c, 1, 300.0, 300.0);
+        touch_down(&mut c, 2, 500.0, 300.0);
+        touch_move(&mut c, 1, 350.0, 320.0);
+        touch_move(&mut c, 2, 550.0, 320.0);
+
+        assert_close(c.zoom, 1.0);
+        assert_close(c.pan_x, 50.0);
+        assert_close(c.pan_y, 20.0);
+        assert!(c.elements.is_empty() && c.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn single_finger_draws() {
+        let mut c = CanvasInner::for_test();
+        c.set_tool_mode("pen");
+        touch_down(&mut c, 1, 100.0, 100.0);
+        assert!(c.interaction == Interaction::Drawing);
+        touch_move(&mut c, 1, 120.0, 110.0);
+        touch_up(&mut c, 1, 120.0, 110.0);
+
+        assert_eq!(c.elements.len(), 1);
+        assert_eq!(c.undo_stack.len(), 1);
+        assert!(c.interaction == Interaction::Idle);
+    }
+
+    #[test]
+    fn second_finger_cancels_stroke_until_all_fingers_lift() {
+        let mut c = CanvasInner::for_test();
+        c.set_tool_mode("pen");
+        touch_down(&mut c, 1, 100.0, 100.0);
+        touch_move(&mut c, 1, 120.0, 110.0);
+        touch_down(&mut c, 2, 300.0, 100.0);
+
+        assert!(c.interaction == Interaction::Pinching);
+        assert!(c.current_element.is_none());
+
+        // 핀치 후 남은 손가락은 그리지 않음
+        touch_up(&mut c, 2, 300.0, 100.0);
+        touch_move(&mut c, 1, 140.0, 130.0);
+        touch_up(&mut c, 1, 140.0, 130.0);
+        assert!(c.elements.is_empty() && c.undo_stack.is_empty());
+
+        // 모든 손가락이 떨어진 뒤의 새 터치는 다시 그림
+        touch_down(&mut c, 3, 100.0, 100.0);
+        touch_move(&mut c, 3, 120.0, 110.0);
+        touch_up(&mut c, 3, 120.0, 110.0);
+        assert_eq!(c.elements.len(), 1);
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::BoundingBox;
+use crate::svg_export::escape_xml;
+use crate::CanvasInner;
+
+// ===== 배경 그리드 / 그리드 스냅 =====
+
+/// 그리드 선이 이보다 촘촘하게 보이면 간격을 키움 (스크린 px)
+const MIN_SCREEN_SPACING: f64 = 8.0;
+
+/// 허용하는 최소 그리드 간격 (월드 단위)
+const MIN_SPACING: f64 = 1.0;
+
+/// SVG 내보내기에서 한 축에 그리는 최대 칸 수 (넘으면 간격을 키움)
+const MAX_EXPORT_CELLS: f64 = 200.0;
+
+/// 그리드 표시 방식
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum GridStyle {
+    Lines,
+    Dots,
+}
+
+/// 그리드 설정 (JSON으로 부분 지정 가능)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct GridConfig {
+    pub(crate) visible: bool,
+    pub(crate) style: GridStyle,
+    /// 기본 간격 (월드 단위, 스냅 단위이기도 함)
+    pub(crate) spacing: f64,
+    /// N칸마다 주 그리드선
+    pub(crate) major_every: u32,
+    pub(crate) color: String,
+    pub(crate) major_color: String,
+    pub(crate) snap: bool,
+    /// SVG 내보내기에 그리드 포함
+    pub(crate) export: bool,
+}
+
+impl Default for GridConfig {
+    fn default() -> GridConfig {
+        GridConfig {
+            visible: false,
+            style: GridStyle::Lines,
+            spacing: 20.0,
+            major_every: 5,
+            color: "#eef0f3".to_string(),
+            major_color: "#d9dde3".to_string(),
+            snap: false,
+            export: false,
+        }
+    }
+}
+
+impl GridConfig {
+    /// 값을 가장 가까운 그리드 눈금으로
+    pub(crate) fn snap_value(&self, v: f64) -> f64 {
+        (v / self.spacing).round() * self.spacing
+    }
+
+    /// 줌에 맞춘 표시 간격 (너무 촘촘하면 주 그리드 배수로 건너뜀)
+    fn display_spacing(&self, zoom: f64) -> (f64, u32) {
+        let factor = self.major_every.max(2) as f64;
+        let mut spacing = self.spacing;
+        while spacing * zoom < MIN_SCREEN_SPACING {
+            spacing *= factor;
+        }
+        (spacing, self.major_every.max(1))
+    }
+
+    /// 내보내기 간격 (extent 안에 MAX_EXPORT_CELLS칸 이하가 되도록 주 그리드 배수로 건너뜀)
+    fn export_spacing(&self, extent: f64) -> f64 {
+        let factor = self.major_every.max(2) as f64;
+        let mut spacing = self.spacing;
+        while extent / spacing > MAX_EXPORT_CELLS {
+            spacing *= factor;
+        }
+        spacing
+    }
+}
+
+impl CanvasInner {
+    /// 스냅이 켜져 있으면 그리드에 맞춘 좌표
+    pub(crate) fn snap_to_grid(&self, x: f64, y: f64) -> (f64, f64) {
+        if !self.grid.snap {
+            return (x, y);
+        }
+        (self.grid.snap_value(x), self.grid.snap_value(y))
+    }
+
+    /// 그리드 설정 적용 (JSON, 생략한 필드는 기본값)
+    pub(crate) fn set_grid_config(&mut self, json: &str) -> Result<(), String> {
+        let config: GridConfig = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if !config.spacing.is_finite() || config.spacing < MIN_SPACING {
+            return Err(format!(
+                "grid spacing must be a finite number >= {}",
+                MIN_SPACING
+            ));
+        }
+        self.grid = config;
+        self.needs_render = true;
+        Ok(())
+    }
+
+    /// 현재 그리드 설정 JSON
+    pub(crate) fn get_grid_config(&self) -> String {
+        serde_json::to_string(&self.grid).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// 화면에 보이는 월드 영역
+    pub(crate) fn visible_world_bounds(&self) -> BoundingBox {
+        BoundingBox {
+            min_x: self.screen_to_world_x(0.0),
+            min_y: self.screen_to_world_y(0.0),
+            max_x: self.screen_to_world_x(self.logical_width),
+            max_y: self.screen_to_world_y(self.logical_height),
+        }
+    }
+
+    /// 배경 그리드 그리기 (월드 스페이스, 줌에 따라 밀도 조절)
+    pub(crate) fn draw_grid(&self) {
+        if !self.grid.visible {
+            return;
+        }
+
+        let (spacing, major_every) = self.grid.display_spacing(self.zoom);
+        let view = self.visible_world_bounds();
+        let px = 1.0 / self.zoom;
+
+        let first_col = (view.min_x / spacing).floor() as i64;
+        let last_col = (view.max_x / spacing).ceil() as i64;
+        let first_row = (view.min_y / spacing).floor() as i64;
+        let last_row = (view.max_y / spacing).ceil() as i64;
+        let is_major = |i: i64| i.rem_euclid(major_every as i64) == 0;
+
+        self.ctx.save();
+
+        match self.grid.style {
+            GridStyle::Lines => {
+                // 보조선 먼저, 주 그리드선을 위에
+                for major in [false, true] {
+                    self.ctx.set_stroke_style_str(if major {
+                        &self.grid.major_color
+                    } else {
+                        &self.grid.color
+                    });
+                    self.ctx.set_line_width(px);
+                    self.ctx.begin_path();
+                    for col in (first_col..=last_col).filter(|c| is_major(*c) == major) {
+                        let x = col as f64 * spacing;
+                        self.ctx.move_to(x, view.min_y);
+                        self.ctx.line_to(x, view.max_y);
+                    }
+                    for row in (first_row..=last_row).filter(|r| is_major(*r) == major) {
+                        let y = row as f64 * spacing;
+                        self.ctx.move_to(view.min_x, y);
+                        self.ctx.line_to(view.max_x, y);
+                    }
+                    self.ctx.stroke();
+                }
+            }
+            GridStyle::Dots => {
+                for row in first_row..=last_row {
+                    for col in first_col..=last_col {
+                        let major = is_major(row) && is_major(col);
+                        let size = if major { 2.5 * px } else { 1.5 * px };
+                        self.ctx.set_fill_style_str(if major {
+                            &self.grid.major_color
+                        } else {
+                            &self.grid.color
+                        });
+                        self.ctx.fill_rect(
+                            col as f64 * spacing - size / 2.0,
+                            row as f64 * spacing - size / 2.0,
+                            size,
+                            size,
+                        );
+                    }
+                }
+            }
+        }
+
+        self.ctx.restore();
+    }
+
+    /// SVG용 그리드 (내보내기 옵션이 켜져 있을 때만, 넓은 영역은 간격을 키워 개수 제한)
+    pub(crate) fn grid_svg(&self, vx: f64, vy: f64, vw: f64, vh: f64) -> String {
+        if !self.grid.visible || !self.grid.export {
+            return String::new();
+        }
+
+        let spacing = self.grid.export_spacing(vw.max(vh));
+        let major_every = self.grid.major_every.max(1) as i64;
+        let minor_color = escape_xml(&self.grid.color);
+        let major_color = escape_xml(&self.grid.major_color);
+        let first_col = (vx / spacing).ceil() as i64;
+        let last_col = ((vx + vw) / spacing).floor() as i64;
+        let first_row = (vy / spacing).ceil() as i64;
+        let last_row = ((vy + vh) / spacing).floor() as i64;
+
+        let mut svg = String::from("<g>");
+        match self.grid.style {
+            GridStyle::Lines => {
+                for col in first_col..=last_col {
+                    let x = col as f64 * spacing;
+                    let color = if col.rem_euclid(major_every) == 0 {
+                        &major_color
+                    } else {
+                        &minor_color
+                    };
+                    let _ = write!(
+                        svg,
+                        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>"#,
+                        x,
+                        vy,
+                        x,
+                        vy + vh,
+                        color
+                    );
+                }
+                for row in first_row..=last_row {
+                    let y = row as f64 * spacing;
+                    let color = if row.rem_euclid(major_every) == 0 {
+                        &major_color
+                    } else {
+                        &minor_color
+                    };
+                    let _ = write!(
+                        svg,
+                        r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="1"/>"#,
+                        vx,
+                        y,
+                        vx + vw,
+                        y,
+                        color
+                    );
+                }
+            }
+            GridStyle::Dots => {
+                for row in first_row..=last_row {
+                    for col in first_col..=last_col {
+                        let major =
+                            row.rem_euclid(major_every) == 0 && col.rem_euclid(major_every) == 0;
+                        let _ = write!(
+                            svg,
+                            r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                            col as f64 * spacing,
+                            row as f64 * spacing,
+                            if major { 1.25 } else { 0.75 },
+                            if major { &major_color } else { &minor_color }
+                        );
+                    }
+                }
+            }
+        }
+        svg.push_str("</g>");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_spacing() {
+        let mut c = CanvasInner::for_test();
+        for json in [
+            r#"{"spacing": 0}"#,
+            r#"{"spacing": -5}"#,
+            r#"{"spacing": 0.01}"#,
+            r#"{"spacing": 1e400}"#,
+        ] {
+            assert!(c.set_grid_config(json).is_err(), "{}", json);
+        }
+        assert_eq!(c.grid.spacing, 20.0);
+
+        c.set_grid_config(r#"{"spacing": 8, "snap": true}"#)
+            .unwrap();
+        assert_eq!(c.snap_to_grid(13.0, -3.0), (16.0, -0.0));
+    }
+
+    #[test]
+    fn export_grid_is_bounded() {
+        let mut c = CanvasInner::for_test();
+        for style in ["lines", "dots"] {
+            let json = format!(
+                r#"{{"visible": true, "export": true, "spacing": 1, "style": "{}"}}"#,
+                style
+            );
+            c.set_grid_config(&json).unwrap();
+            let svg = c.grid_svg(0.0, 0.0, 100_000.0, 100_000.0);
+            let count = svg.matches("<line").count() + svg.matches("<circle").count();
+            let max = (MAX_EXPORT_CELLS as usize + 1).pow(2);
+            assert!(count > 0 && count <= max, "{}: {}", style, count);
+        }
+
+        // 좁은 영역은 원래 간격 그대로
+        c.set_grid_config(r#"{"visible": true, "export": true, "spacing": 10}"#)
+            .unwrap();
+        let svg = c.grid_svg(0.0, 0.0, 100.0, 50.0);
+        assert_eq!(svg.matches("<line").count(), 11 + 6);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::HashSet;
+
+use crate::models::{Element, Shape, Style};
+use crate::{Action, CanvasInner};
+
+// ===== 그룹 (중첩 가능) =====
+
+/// 요소 트리에서 ID로 요소 찾기 (그룹 내부까지 탐색)
+pub(crate) fn find_element(elements: &[Element], id: u32) -> Option<&Element> {
+    for e in elements {
+        if e.id == id {
+            return Some(e);
+        }
+        if let Shape::Group { children } = &e.shape {
+            if let Some(found) = find_element(children, id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// 요소 트리에서 ID로 그룹의 자식 목록 찾기
+pub(crate) fn group_children(elements: &[Element], id: u32) -> Option<&Vec<Element>> {
+    match &find_element(elements, id)?.shape {
+        Shape::Group { children } => Some(children),
+        _ => None,
+    }
+}
+
+/// 요소 트리에서 ID로 그룹의 자식 목록 찾기 (수정용)
+pub(crate) fn group_children_mut(elements: &mut [Element], id: u32) -> Option<&mut Vec<Element>> {
+    for e in elements.iter_mut() {
+        if let Shape::Group { children } = &mut e.shape {
+            if e.id == id {
+                return Some(children);
+            }
+            if let Some(found) = group_children_mut(children, id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// 선택 요소들을 그룹 하나로 묶음 (members: 원래 인덱스, group: 묶인 결과, index: 그룹 위치)
+pub(crate) fn apply_group(
+    elements: &mut Vec<Element>,
+    members: &[(usize, Element)],
+    group: &Element,
+    index: usize,
+) {
+    let ids: HashSet<u32> = members.iter().map(|(_, e)| e.id).collect();
+    elements.retain(|e| !ids.contains(&e.id));
+    elements.insert(index.min(elements.len()), group.clone());
+}
+
+/// apply_group 되돌리기 (그룹 제거 후 멤버를 원래 인덱스에 복원)
+pub(crate) fn revert_group(
+    elements: &mut Vec<Element>,
+    members: &[(usize, Element)],
+    group: &Element,
+) {
+    elements.retain(|e| e.id != group.id);
+    for (idx, e) in members {
+        elements.insert((*idx).min(elements.len()), e.clone());
+    }
+}
+
+/// 그룹들을 풀어 자식을 그룹 자리에 삽입 (groups: 원래 인덱스 오름차순)
+pub(crate) fn apply_ungroup(elements: &mut Vec<Element>, groups: &[(usize, Element)]) {
+    for (idx, group) in groups.iter().rev() {
+        let Shape::Group { children } = &group.shape else {
+            continue;
+        };
+        elements.retain(|e| e.id != group.id);
+        let at = (*idx).min(elements.len());
+        // 자식은 그룹의 레이어를 이어받음
+        let children = children.iter().cloned().map(|mut c| {
+            c.layer = group.layer;
+            c
+        });
+        elements.splice(at..at, children);
+    }
+}
+
+/// apply_ungroup 되돌리기
+pub(crate) fn revert_ungroup(elements: &mut Vec<Element>, groups: &[(usize, Element)]) {
+    for (idx, group) in groups {
+        let Shape::Group { children } = &group.shape else {
+            continue;
+        };
+        let ids: HashSet<u32> = children.iter().map(|c| c.id).collect();
+        elements.retain(|e| !ids.contains(&e.id));
+        elements.insert((*idx).min(elements.len()), group.clone());
+    }
+}
+
+impl CanvasInner {
+    /// 현재 편집 범위의 요소 목록 (그룹에 들어가 있으면 그 그룹의 자식)
+    pub(crate) fn scope(&self) -> &[Element] {
+        match self.entered_groups.last() {
+            Some(&id) => group_children(&self.elements, id).map_or(&[], |c| c.as_slice()),
+            None => &self.elements,
+        }
+    }
+
+    /// 현재 편집 범위의 요소 목록 (수정용)
+    pub(crate) fn scope_mut(&mut self) -> &mut Vec<Element> {
+        match self.entered_groups.last() {
+            Some(&id) if group_children(&self.elements, id).is_some() => {
+                group_children_mut(&mut self.elements, id).unwrap()
+            }
+            _ => &mut self.elements,
+        }
+    }
+
+    /// 현재 편집 범위를 대상으로 하는 액션으로 감쌈
+    pub(crate) fn scoped(&self, action: Action) -> Action {
+        match self.entered_groups.last() {
+            Some(&group) => Action::InGroup {
+                group,
+                action: Box::new(action),
+            },
+            None => action,
+        }
+    }
+
+    /// 선택된 요소들을 그룹으로 묶음 (2개 이상). 묶었으면 true
+    pub(crate) fn group_selected(&mut self) -> bool {
+        let members: Vec<(usize, Element)> = self
+            .scope()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| self.selected_ids.contains(&e.id))
+            .map(|(i, e)| (i, e.clone()))
+            .collect();
+        if members.len() < 2 {
+            return false;
+        }
+
+        // 가장 위 멤버 자리에 그룹 배치
+        let index = members[members.len() - 1].0 + 1 - members.len();
+        let group = Element {
+            id: self.next_id,
+            shape: Shape::Group {
+                children: members.iter().map(|(_, e)| e.clone()).collect(),
+            },
+            style: Style {
+                color: self.color.clone(),
+                width: self.line_width,
+            },
+            layer: members[0].1.layer,
+            locked: false,
+            hidden: false,
+        };
+        self.next_id += 1;
+
+        self.selected_ids.clear();
+        self.selected_ids.insert(group.id);
+        self.selection_anchor = Some(group.id);
+
+        let action = self.scoped(Action::GroupElements {
+            members,
+            group,
+            index,
+        });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.needs_render = true;
+        true
+    }
+
+    /// 선택된 그룹들을 해제하고 자식들을 선택. 해제했으면 true
+    pub(crate) fn ungroup_selected(&mut self) -> bool {
+        let groups: Vec<(usize, Element)> = self
+            .scope()
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                self.selected_ids.contains(&e.id) && matches!(e.shape, Shape::Group { .. })
+            })
+            .map(|(i, e)| (i, e.clone()))
+            .collect();
+        if groups.is_empty() {
+            return false;
+        }
+
+        for (_, group) in &groups {
+            self.selected_ids.remove(&group.id);
+            if let Shape::Group { children } = &group.shape {
+                self.selected_ids.extend(children.iter().map(|c| c.id));
+            }
+        }
+
+        let action = self.scoped(Action::UngroupElements { groups });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.drop_uninteractive_selection();
+        self.needs_render = true;
+        true
+    }
+
+    /// 좌표의 그룹에 들어가 그 안의 요소를 선택 (더블클릭).
+    /// 그룹이 아닌 곳이면 한 단계 밖으로 나감. 범위가 바뀌었으면 true
+    pub(crate) fn enter_group_at(&mut self, x: f64, y: f64) -> bool {
+        let hit = self
+            .pickable()
+            .into_iter()
+            .rev()
+            .find(|e| e.hit_test(x, y))
+            .map(|e| (e.id, matches!(e.shape, Shape::Group { .. })));
+
+        match hit {
+            Some((id, true)) => {
+                self.entered_groups.push(id);
+                self.selected_ids.clear();
+                self.last_duplicate = None;
+                self.try_select_at(x, y, false);
+                true
+            }
+            Some((_, false)) => false,
+            None => self.exit_group(),
+        }
+    }
+
+    /// 한 단계 밖으로 나가고 나온 그룹을 선택. 그룹 안이 아니면 false
+    pub(crate) fn exit_group(&mut self) -> bool {
+        let Some(id) = self.entered_groups.pop() else {
+            return false;
+        };
+        self.selected_ids.clear();
+        self.selected_ids.insert(id);
+        self.selection_anchor = Some(id);
+        self.last_duplicate = None;
+        self.needs_render = true;
+        true
+    }
+
+    /// undo/동기화 등으로 들어가 있던 그룹이 사라졌으면 범위를 정리
+    pub(crate) fn validate_scope(&mut self) {
+        if let Some(pos) = self
+            .entered_groups
+            .iter()
+            .position(|id| group_children(&self.elements, *id).is_none())
+        {
+            self.entered_groups.truncate(pos);
+        }
+    }
+
+    /// 들어가 있는 그룹의 범위 표시 (월드 스페이스)
+    pub(crate) fn draw_group_scope(&self) {
+        let Some(&id) = self.entered_groups.last() else {
+            return;
+        };
+        let Some(bb) = find_element(&self.elements, id).and_then(|e| e.bounding_box()) else {
+            return;
+        };
+        let px = 1.0 / self.zoom;
+        let padding = 10.0 * px;
+
+        self.ctx.save();
+        let _ = self.ctx.set_line_dash(&self.dash_selection);
+        self.ctx.set_stroke_style_str("#94a3b8");
+        self.ctx.set_line_width(px);
+        self.ctx.begin_path();
+        self.ctx.rect(
+            bb.min_x - padding,
+            bb.min_y - padding,
+            (bb.max_x - bb.min_x) + padding * 2.0,
+            (bb.max_y - bb.min_y) + padding * 2.0,
+        );
+        self.ctx.stroke();
+        self.ctx.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(id: u32) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect {
+                x: id as f64 * 20.0,
+                y: 0.0,
+                w: 10.0,
+                h: 10.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    /// 요소 1..=n이 순서대로 있는 캔버스
+    fn canvas(n: u32) -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.elements = (1..=n).map(rect).collect();
+        c.next_id = n + 1;
+        c
+    }
+
+    /// 그리기 순서 (그룹은 대괄호)
+    fn tree(elements: &[Element]) -> String {
+        elements
+            .iter()
+            .map(|e| match &e.shape {
+                Shape::Group { children } => format!("{}[{}]", e.id, tree(children)),
+                _ => e.id.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn select(c: &mut CanvasInner, ids: &[u32]) {
+        c.selected_ids = ids.iter().copied().collect();
+    }
+
+    #[test]
+    fn group_takes_topmost_member_position() {
+        let mut c = canvas(5);
+        select(&mut c, &[2, 4]);
+        assert!(c.group_selected());
+        assert_eq!(tree(&c.elements), "1 3 6[2 4] 5");
+        assert_eq!(c.selected_ids, [6].into());
+
+        assert!(c.ungroup_selected());
+        assert_eq!(tree(&c.elements), "1 3 2 4 5");
+        assert_eq!(c.selected_ids, [2, 4].into());
+
+        c.undo();
+        assert_eq!(tree(&c.elements), "1 3 6[2 4] 5");
+        c.undo();
+        assert_eq!(tree(&c.elements), "1 2 3 4 5");
+        c.redo();
+        c.redo();
+        assert_eq!(tree(&c.elements), "1 3 2 4 5");
+    }
+
+    #[test]
+    fn ungrouping_several_groups_keeps_each_in_place() {
+        let mut c = canvas(6);
+        select(&mut c, &[1, 2]);
+        c.group_selected();
+        select(&mut c, &[4, 5]);
+        c.group_selected();
+        assert_eq!(tree(&c.elements), "7[1 2] 3 8[4 5] 6");
+
+        select(&mut c, &[7, 8]);
+        assert!(c.ungroup_selected());
+        assert_eq!(tree(&c.elements), "1 2 3 4 5 6");
+        c.undo();
+        assert_eq!(tree(&c.elements), "7[1 2] 3 8[4 5] 6");
+
+        // 그룹이 아니거나 하나뿐이면 아무것도 하지 않음
+        select(&mut c, &[3]);
+        assert!(!c.ungroup_selected());
+        assert!(!c.group_selected());
+        assert_eq!(c.undo_stack.len(), 2);
+    }
+
+    #[test]
+    fn grouping_inside_entered_group_is_undoable() {
+        let mut c = canvas(4);
+        select(&mut c, &[1, 2, 3]);
+        c.group_selected();
+        c.entered_groups.push(5);
+
+        select(&mut c, &[2, 3]);
+        assert!(c.group_selected());
+        assert_eq!(tree(&c.elements), "5[1 6[2 3]] 4");
+        assert_eq!(tree(c.scope()), "1 6[2 3]");
+
+        select(&mut c, &[6]);
+        assert!(c.ungroup_selected());
+        assert_eq!(tree(&c.elements), "5[1 2 3] 4");
+
+        c.undo();
+        assert_eq!(tree(&c.elements), "5[1 6[2 3]] 4");
+        c.undo();
+        assert_eq!(tree(&c.elements), "5[1 2 3] 4");
+        assert_eq!(c.entered_groups, vec![5]);
+
+        // 바깥 그룹까지 되돌리면 범위도 정리됨
+        c.undo();
+        assert_eq!(tree(&c.elements), "1 2 3 4");
+        assert!(c.entered_groups.is_empty());
+
+        c.redo();
+        c.redo();
+        assert_eq!(tree(&c.elements), "5[1 6[2 3]] 4");
+    }
+}
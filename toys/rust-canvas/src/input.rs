@@ -0,0 +1,501 @@
+use crate::{CanvasInner, ToolMode};
+
+// ===== 포인터 입력 상태 머신 =====
+
+/// 수정자 키 비트 플래그 (pointer_*/key_down의 modifiers 인자)
+pub(crate) const MOD_SHIFT: u32 = 1;
+pub(crate) const MOD_CTRL: u32 = 2;
+pub(crate) const MOD_ALT: u32 = 4;
+pub(crate) const MOD_META: u32 = 8;
+
+/// PointerEvent.buttons 비트
+const BUTTON_PRIMARY: u32 = 1;
+const BUTTON_MIDDLE: u32 = 4;
+
+/// 진행 중인 포인터 인터랙션
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Interaction {
+    Idle,
+    Drawing,
+    DrawingShape,
+    Moving,
+    RubberBand,
+    Lasso,
+    Panning,
+    /// 멀티터치 핀치 줌 / 두 손가락 팬
+    Pinching,
+}
+
+impl Interaction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Interaction::Idle => "idle",
+            Interaction::Drawing => "drawing",
+            Interaction::DrawingShape => "drawing_shape",
+            Interaction::Moving => "moving",
+            Interaction::RubberBand => "rubber_band",
+            Interaction::Lasso => "lasso",
+            Interaction::Panning => "panning",
+            Interaction::Pinching => "pinching",
+        }
+    }
+}
+
+impl CanvasInner {
+    /// 포인터 다운 (스크린 좌표) — 도구/상태에 따라 인터랙션 시작
+    pub(crate) fn pointer_down(
+        &mut self,
+        sx: f64,
+        sy: f64,
+        buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        // 멀티터치 제스처가 소비한 이벤트
+        if pointer_type == "touch" && self.gesture_pointer_down(pointer_id, sx, sy) {
+            return;
+        }
+        if self.interaction != Interaction::Idle {
+            return;
+        }
+
+        // Space 팬 모드 또는 중간 버튼 (타임랩스 재생 중에는 편집 대신 항상 팬)
+        if self.space_pan || buttons & BUTTON_MIDDLE != 0 || self.replay.is_some() {
+            self.start_pan(sx, sy);
+            self.interaction = Interaction::Panning;
+            self.active_pointer = pointer_id;
+            self.update_cursor_style(sx, sy);
+            return;
+        }
+        // 우클릭, 펜 지우개 버튼 등은 그리거나 선택하지 않음
+        if buttons & BUTTON_PRIMARY == 0 {
+            return;
+        }
+
+        let wx = self.screen_to_world_x(sx);
+        let wy = self.screen_to_world_y(sy);
+        let shift = modifiers & MOD_SHIFT != 0;
+
+        self.interaction = match self.tool_mode {
+            ToolMode::Select => {
+                // Shift 클릭은 이동 대신 선택 토글
+                if !shift && self.has_selection() && self.is_over_selected(wx, wy) {
+                    self.start_move(wx, wy);
+                    Interaction::Moving
+                } else {
+                    let hit = self.try_select_at(wx, wy, shift);
+                    if hit && self.is_over_selected(wx, wy) {
+                        self.start_move(wx, wy);
+                        Interaction::Moving
+                    } else if !hit && self.lasso_select {
+                        // 러버밴드/올가미는 스크린 좌표
+                        self.start_lasso(sx, sy);
+                        Interaction::Lasso
+                    } else if !hit {
+                        self.start_rubber_band(sx, sy);
+                        Interaction::RubberBand
+                    } else {
+                        Interaction::Idle
+                    }
+                }
+            }
+            ToolMode::Line | ToolMode::Rectangle | ToolMode::Circle => {
+                self.start_shape(wx, wy);
+                self.set_shape_modifiers(modifiers);
+                Interaction::DrawingShape
+            }
+            ToolMode::Pen | ToolMode::Eraser => {
+                self.start_drawing(wx, wy);
+                Interaction::Drawing
+            }
+        };
+        self.active_pointer = pointer_id;
+        self.update_cursor_style(sx, sy);
+    }
+
+    /// 포인터 이동 (스크린 좌표)
+    pub(crate) fn pointer_move(
+        &mut self,
+        sx: f64,
+        sy: f64,
+        _buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        if pointer_type == "touch" && self.gesture_pointer_move(pointer_id, sx, sy) {
+            return;
+        }
+
+        let wx = self.screen_to_world_x(sx);
+        let wy = self.screen_to_world_y(sy);
+
+        // 다른 포인터의 이동은 진행 중인 인터랙션에 반영하지 않음
+        let interaction = if pointer_id == self.active_pointer {
+            self.interaction
+        } else {
+            Interaction::Idle
+        };
+        match interaction {
+            Interaction::Panning => self.update_pan(sx, sy),
+            Interaction::RubberBand => self.update_rubber_band(sx, sy),
+            Interaction::Lasso => self.update_lasso(sx, sy),
+            Interaction::Moving => self.move_selected(wx, wy),
+            Interaction::DrawingShape => self.update_shape(wx, wy, modifiers),
+            Interaction::Drawing => self.draw(wx, wy),
+            Interaction::Pinching | Interaction::Idle => {}
+        }
+
+        // 브러시 커서 미리보기 (터치에는 호버가 없음), 선택 도구는 위치만 기록
+        if pointer_type != "touch" {
+            if self.tool_mode != ToolMode::Select && !self.space_pan {
+                self.update_cursor(sx, sy);
+            } else {
+                self.track_cursor(sx, sy);
+            }
+        }
+        self.update_cursor_style(sx, sy);
+    }
+
+    /// 포인터 업 (스크린 좌표) — 진행 중인 인터랙션 확정
+    pub(crate) fn pointer_up(
+        &mut self,
+        sx: f64,
+        sy: f64,
+        _buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        if pointer_type == "touch" && self.gesture_pointer_up(pointer_id) {
+            return;
+        }
+        // 다른 포인터가 떨어져도 진행 중인 드래그는 유지
+        if pointer_id != self.active_pointer {
+            return;
+        }
+        self.finish_interaction(modifiers);
+        self.update_cursor_style(sx, sy);
+    }
+
+    /// 더블클릭 (스크린 좌표) — 선택 도구에서 그룹 진입/탈출
+    pub(crate) fn pointer_double_click(&mut self, sx: f64, sy: f64) -> bool {
+        if self.tool_mode != ToolMode::Select || self.interaction != Interaction::Idle {
+            return false;
+        }
+        let wx = self.screen_to_world_x(sx);
+        let wy = self.screen_to_world_y(sy);
+        self.enter_group_at(wx, wy)
+    }
+
+    /// 포인터가 캔버스를 벗어남 — 인터랙션 확정 후 커서 숨김
+    pub(crate) fn pointer_leave(&mut self) {
+        let modifiers = self.shape_modifiers;
+        self.finish_interaction(modifiers);
+        self.hide_cursor();
+    }
+
+    /// 현재 인터랙션 종료 (modifiers: 확정 시점의 수정자 키)
+    pub(crate) fn finish_interaction(&mut self, modifiers: u32) {
+        match self.interaction {
+            Interaction::Panning => self.stop_pan(),
+            Interaction::RubberBand => self.finish_rubber_band(modifiers & MOD_SHIFT != 0),
+            Interaction::Lasso => self.finish_lasso(modifiers & MOD_SHIFT != 0),
+            Interaction::Moving => self.stop_move(),
+            Interaction::DrawingShape => self.finish_shape(modifiers),
+            Interaction::Drawing => self.stop_drawing(),
+            Interaction::Pinching | Interaction::Idle => {}
+        }
+        self.interaction = Interaction::Idle;
+    }
+
+    /// 진행 중인 드래그를 모두 확정 (도구 전환 등). 포인터 상태 기계를 거치지 않고
+    /// JS가 직접 시작한 드래그(start_move, start_shape 등)도 함께 마무리
+    pub(crate) fn finish_all_drags(&mut self) {
+        let modifiers = self.shape_modifiers;
+        self.finish_interaction(modifiers);
+        self.stop_move();
+        self.finish_rubber_band(false);
+        self.finish_lasso(false);
+        self.finish_shape(modifiers);
+        if self.is_drawing {
+            self.stop_drawing();
+        }
+        self.stop_pan();
+        self.needs_render = true;
+    }
+
+    /// 포인터 취소 (pointercancel) — 확정하지 않고 되돌림
+    pub(crate) fn pointer_cancel(&mut self, pointer_id: i32) {
+        let consumed = self.gesture_pointer_up(pointer_id);
+        if consumed || pointer_id == self.active_pointer {
+            self.cancel_interaction();
+        }
+    }
+
+    /// 현재 인터랙션을 확정 없이 취소 (두 번째 손가락이 닿았을 때 등)
+    pub(crate) fn cancel_interaction(&mut self) {
+        match self.interaction {
+            Interaction::Drawing => {
+                self.is_drawing = false;
+                self.current_element = None;
+            }
+            Interaction::DrawingShape => self.is_drawing_shape = false,
+            Interaction::Moving => {
+                // 드래그로 이동한 만큼 되돌림 (undo 기록 없음)
+                let (dx, dy) = (self.move_total_dx, self.move_total_dy);
+                self.translate_selected(-dx, -dy);
+                self.is_moving = false;
+            }
+            Interaction::RubberBand => self.is_rubber_band = false,
+            Interaction::Lasso => {
+                self.is_lasso = false;
+                self.lasso_points.clear();
+            }
+            Interaction::Panning => self.stop_pan(),
+            Interaction::Pinching | Interaction::Idle => {}
+        }
+        self.clear_snap_guides();
+        self.interaction = Interaction::Idle;
+        self.needs_render = true;
+    }
+
+    /// Space 팬 모드 설정 (키 떼면 진행 중인 팬도 종료)
+    pub(crate) fn set_space_pan(&mut self, active: bool) {
+        self.space_pan = active;
+        if !active && self.interaction == Interaction::Panning {
+            self.finish_interaction(0);
+        }
+        self.cursor_style = if active {
+            "grab"
+        } else {
+            self.idle_cursor_style()
+        };
+        self.needs_render = true;
+    }
+
+    /// 도구별 기본 CSS 커서
+    pub(crate) fn idle_cursor_style(&self) -> &'static str {
+        if self.tool_mode == ToolMode::Select {
+            "default"
+        } else {
+            "none"
+        }
+    }
+
+    /// 호스트가 적용할 CSS 커서 갱신
+    fn update_cursor_style(&mut self, sx: f64, sy: f64) {
+        self.cursor_style = match self.interaction {
+            Interaction::Panning => "grabbing",
+            Interaction::Moving => "move",
+            _ if self.space_pan => "grab",
+            Interaction::Idle if self.tool_mode == ToolMode::Select => {
+                let wx = self.screen_to_world_x(sx);
+                let wy = self.screen_to_world_y(sy);
+                if self.has_selection() && self.is_over_selected(wx, wy) {
+                    "move"
+                } else {
+                    "default"
+                }
+            }
+            _ => self.idle_cursor_style(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Element, Shape, Style};
+
+    fn canvas() -> CanvasInner {
+        CanvasInner::for_test()
+    }
+
+    /// (10, 10)–(50, 50) 사각형 하나가 있는 선택 도구 캔버스 (윤곽선에서만 선택됨)
+    fn canvas_with_rect() -> CanvasInner {
+        let mut c = canvas();
+        c.set_tool_mode("select");
+        c.elements.push(Element {
+            id: 1,
+            shape: Shape::Rect {
+                x: 10.0,
+                y: 10.0,
+                w: 40.0,
+                h: 40.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 2.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        });
+        c.next_id = 2;
+        c
+    }
+
+    fn rect_origin(c: &CanvasInner) -> (f64, f64) {
+        match c.elements[0].shape {
+            Shape::Rect { x, y, .. } => (x, y),
+            _ => unreachable!(),
+        }
+    }
+
+    fn down(c: &mut CanvasInner, x: f64, y: f64) {
+        c.pointer_down(x, y, 1, 0, "mouse", 1);
+    }
+
+    fn drag_to(c: &mut CanvasInner, x: f64, y: f64) {
+        c.pointer_move(x, y, 1, 0, "mouse", 1);
+    }
+
+    #[test]
+    fn drag_moves_selection_with_one_undo_entry() {
+        let mut c = canvas_with_rect();
+        down(&mut c, 10.0, 30.0);
+        assert!(c.interaction == Interaction::Moving);
+        drag_to(&mut c, 15.0, 32.0);
+        drag_to(&mut c, 23.0, 37.0);
+        c.pointer_up(23.0, 37.0, 0, 0, "mouse", 1);
+
+        assert!(c.interaction == Interaction::Idle);
+        assert_eq!(rect_origin(&c), (23.0, 17.0));
+        assert_eq!(c.undo_stack.len(), 1);
+        c.undo();
+        assert_eq!(rect_origin(&c), (10.0, 10.0));
+    }
+
+    #[test]
+    fn pointer_cancel_reverts_move() {
+        let mut c = canvas_with_rect();
+        down(&mut c, 10.0, 30.0);
+        drag_to(&mut c, 40.0, 40.0);
+        c.pointer_cancel(1);
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.is_moving);
+        assert_eq!(rect_origin(&c), (10.0, 10.0));
+        assert!(c.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn tool_switch_during_move_commits_it() {
+        let mut c = canvas_with_rect();
+        down(&mut c, 10.0, 30.0);
+        assert!(c.interaction == Interaction::Moving);
+        drag_to(&mut c, 20.0, 40.0);
+        c.set_tool_mode("pen");
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.is_moving);
+        assert_eq!(rect_origin(&c), (20.0, 20.0));
+        assert_eq!(c.undo_stack.len(), 1);
+        c.undo();
+        assert_eq!(rect_origin(&c), (10.0, 10.0));
+
+        // 뒤늦은 pointerup은 아무것도 하지 않음
+        c.pointer_up(30.0, 30.0, 0, 0, "mouse", 1);
+        assert!(c.elements.len() == 1 && c.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn tool_switch_during_rubber_band_clears_overlay() {
+        let mut c = canvas_with_rect();
+        down(&mut c, 300.0, 300.0);
+        assert!(c.interaction == Interaction::RubberBand);
+        drag_to(&mut c, 5.0, 5.0);
+        c.set_tool_mode("rect");
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.is_rubber_band);
+    }
+
+    #[test]
+    fn tool_switch_during_lasso_clears_overlay() {
+        let mut c = canvas_with_rect();
+        c.set_lasso_select(true);
+        down(&mut c, 300.0, 300.0);
+        assert!(c.interaction == Interaction::Lasso);
+        drag_to(&mut c, 320.0, 300.0);
+        drag_to(&mut c, 320.0, 320.0);
+        c.set_tool_mode("pen");
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.is_lasso);
+        assert!(c.lasso_points.is_empty());
+    }
+
+    #[test]
+    fn tool_switch_during_stroke_keeps_it() {
+        let mut c = canvas();
+        c.set_tool_mode("pen");
+        down(&mut c, 100.0, 100.0);
+        assert!(c.interaction == Interaction::Drawing);
+        drag_to(&mut c, 120.0, 110.0);
+        drag_to(&mut c, 140.0, 130.0);
+        c.set_tool_mode("select");
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.is_drawing);
+        assert_eq!(c.elements.len(), 1);
+        assert_eq!(c.undo_stack.len(), 1);
+    }
+
+    #[test]
+    fn non_primary_buttons_do_not_draw() {
+        let mut c = canvas();
+        c.set_tool_mode("pen");
+        // 우클릭, 펜 지우개 버튼
+        for buttons in [2, 32] {
+            c.pointer_down(100.0, 100.0, buttons, 0, "pen", 1);
+            assert!(c.interaction == Interaction::Idle);
+            c.pointer_move(150.0, 100.0, buttons, 0, "pen", 1);
+            c.pointer_up(150.0, 100.0, 0, 0, "pen", 1);
+        }
+        assert!(c.elements.is_empty() && c.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn shift_click_on_selected_element_deselects_it() {
+        let mut c = canvas_with_rect();
+        c.selected_ids.insert(1);
+        c.pointer_down(10.0, 30.0, 1, MOD_SHIFT, "mouse", 1);
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(!c.has_selection());
+    }
+
+    #[test]
+    fn other_pointer_does_not_end_drag() {
+        let mut c = canvas_with_rect();
+        down(&mut c, 10.0, 30.0);
+        drag_to(&mut c, 20.0, 30.0);
+
+        // 두 번째 포인터의 이동/업은 무시
+        c.pointer_move(200.0, 200.0, 1, 0, "pen", 2);
+        c.pointer_up(200.0, 200.0, 0, 0, "pen", 2);
+        assert!(c.interaction == Interaction::Moving);
+        assert_eq!(rect_origin(&c), (20.0, 10.0));
+
+        drag_to(&mut c, 30.0, 30.0);
+        c.pointer_up(30.0, 30.0, 0, 0, "mouse", 1);
+        assert!(c.interaction == Interaction::Idle);
+        assert_eq!(rect_origin(&c), (30.0, 10.0));
+        assert_eq!(c.undo_stack.len(), 1);
+    }
+
+    #[test]
+    fn tool_switch_finishes_directly_started_move() {
+        let mut c = canvas_with_rect();
+        c.selected_ids.insert(1);
+        c.start_move(20.0, 20.0);
+        c.move_selected(30.0, 25.0);
+        c.set_tool_mode("pen");
+
+        assert!(!c.is_moving);
+        assert_eq!(c.undo_stack.len(), 1);
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::BTreeMap;
+
+use crate::input::{Interaction, MOD_ALT, MOD_CTRL, MOD_META, MOD_SHIFT};
+use crate::{CanvasInner, ToolMode};
+
+// ===== 키보드 단축키 (설정 가능한 키맵) =====
+
+/// 단축키로 실행할 수 있는 명령
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Command {
+    ToolSelect,
+    ToolPen,
+    ToolEraser,
+    ToolLine,
+    ToolRect,
+    ToolCircle,
+    Undo,
+    Redo,
+    Copy,
+    Paste,
+    Duplicate,
+    Delete,
+    SelectAll,
+    Deselect,
+    NudgeLeft,
+    NudgeRight,
+    NudgeUp,
+    NudgeDown,
+    NudgeLeftLarge,
+    NudgeRightLarge,
+    NudgeUpLarge,
+    NudgeDownLarge,
+    ZoomIn,
+    ZoomOut,
+    ResetView,
+    FitView,
+    ZoomToSelection,
+    NextBookmark,
+    PrevBookmark,
+    AlignLeft,
+    AlignCenter,
+    AlignRight,
+    AlignTop,
+    AlignMiddle,
+    AlignBottom,
+    DistributeHorizontal,
+    DistributeVertical,
+    FlipHorizontal,
+    FlipVertical,
+    BringToFront,
+    SendToBack,
+    BringForward,
+    SendBackward,
+    Group,
+    Ungroup,
+    LockSelected,
+    UnlockAll,
+    HideSelected,
+    ShowAll,
+    /// 누르고 있는 동안 팬 모드
+    Pan,
+}
+
+/// (설정 이름, 명령) 목록
+const COMMAND_NAMES: &[(&str, Command)] = &[
+    ("tool_select", Command::ToolSelect),
+    ("tool_pen", Command::ToolPen),
+    ("tool_eraser", Command::ToolEraser),
+    ("tool_line", Command::ToolLine),
+    ("tool_rect", Command::ToolRect),
+    ("tool_circle", Command::ToolCircle),
+    ("undo", Command::Undo),
+    ("redo", Command::Redo),
+    ("copy", Command::Copy),
+    ("paste", Command::Paste),
+    ("duplicate", Command::Duplicate),
+    ("delete", Command::Delete),
+    ("select_all", Command::SelectAll),
+    ("deselect", Command::Deselect),
+    ("nudge_left", Command::NudgeLeft),
+    ("nudge_right", Command::NudgeRight),
+    ("nudge_up", Command::NudgeUp),
+    ("nudge_down", Command::NudgeDown),
+    ("nudge_left_large", Command::NudgeLeftLarge),
+    ("nudge_right_large", Command::NudgeRightLarge),
+    ("nudge_up_large", Command::NudgeUpLarge),
+    ("nudge_down_large", Command::NudgeDownLarge),
+    ("zoom_in", Command::ZoomIn),
+    ("zoom_out", Command::ZoomOut),
+    ("reset_view", Command::ResetView),
+    ("fit_view", Command::FitView),
+    ("zoom_to_selection", Command::ZoomToSelection),
+    ("next_bookmark", Command::NextBookmark),
+    ("prev_bookmark", Command::PrevBookmark),
+    ("align_left", Command::AlignLeft),
+    ("align_center", Command::AlignCenter),
+    ("align_right", Command::AlignRight),
+    ("align_top", Command::AlignTop),
+    ("align_middle", Command::AlignMiddle),
+    ("align_bottom", Command::AlignBottom),
+    ("distribute_horizontal", Command::DistributeHorizontal),
+    ("distribute_vertical", Command::DistributeVertical),
+    ("flip_horizontal", Command::FlipHorizontal),
+    ("flip_vertical", Command::FlipVertical),
+    ("bring_to_front", Command::BringToFront),
+    ("send_to_back", Command::SendToBack),
+    ("bring_forward", Command::BringForward),
+    ("send_backward", Command::SendBackward),
+    ("group", Command::Group),
+    ("ungroup", Command::Ungroup),
+    ("lock_selected", Command::LockSelected),
+    ("unlock_all", Command::UnlockAll),
+    ("hide_selected", Command::HideSelected),
+    ("show_all", Command::ShowAll),
+    ("pan", Command::Pan),
+];
+
+/// 기본 키맵 ("Mod"는 Ctrl 또는 Cmd)
+const DEFAULT_BINDINGS: &[(&str, &str)] = &[
+    ("v", "tool_select"),
+    ("p", "tool_pen"),
+    ("e", "tool_eraser"),
+    ("l", "tool_line"),
+    ("r", "tool_rect"),
+    ("o", "tool_circle"),
+    ("Mod+z", "undo"),
+    ("Mod+Shift+z", "redo"),
+    ("Mod+y", "redo"),
+    ("Mod+c", "copy"),
+    ("Mod+v", "paste"),
+    ("Mod+d", "duplicate"),
+    ("Delete", "delete"),
+    ("Backspace", "delete"),
+    ("Mod+a", "select_all"),
+    ("Escape", "deselect"),
+    ("ArrowLeft", "nudge_left"),
+    ("ArrowRight", "nudge_right"),
+    ("ArrowUp", "nudge_up"),
+    ("ArrowDown", "nudge_down"),
+    ("Shift+ArrowLeft", "nudge_left_large"),
+    ("Shift+ArrowRight", "nudge_right_large"),
+    ("Shift+ArrowUp", "nudge_up_large"),
+    ("Shift+ArrowDown", "nudge_down_large"),
+    ("=", "zoom_in"),
+    ("+", "zoom_in"),
+    ("-", "zoom_out"),
+    ("0", "reset_view"),
+    ("1", "fit_view"),
+    ("2", "zoom_to_selection"),
+    ("PageDown", "next_bookmark"),
+    ("PageUp", "prev_bookmark"),
+    ("Alt+Shift+ArrowLeft", "align_left"),
+    ("Alt+Shift+ArrowRight", "align_right"),
+    ("Alt+Shift+ArrowUp", "align_top"),
+    ("Alt+Shift+ArrowDown", "align_bottom"),
+    ("Shift+h", "flip_horizontal"),
+    ("Shift+v", "flip_vertical"),
+    ("Mod+]", "bring_forward"),
+    ("Mod+[", "send_backward"),
+    ("Mod+Shift+}", "bring_to_front"),
+    ("Mod+Shift+{", "send_to_back"),
+    ("Mod+g", "group"),
+    ("Mod+Shift+g", "ungroup"),
+    ("Mod+Shift+l", "lock_selected"),
+    ("Mod+Alt+l", "unlock_all"),
+    ("Mod+Shift+h", "hide_selected"),
+    ("Mod+Alt+h", "show_all"),
+    ("Space", "pan"),
+];
+
+impl Command {
+    fn from_name(name: &str) -> Option<Command> {
+        COMMAND_NAMES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, c)| *c)
+    }
+
+    fn name(&self) -> &'static str {
+        COMMAND_NAMES
+            .iter()
+            .find(|(_, c)| c == self)
+            .map(|(n, _)| *n)
+            .unwrap_or("")
+    }
+
+    /// 문서를 바꾸지 않고 보기만 바꾸는 명령 (타임랩스 재생 중에도 실행)
+    fn is_view(&self) -> bool {
+        matches!(
+            self,
+            Command::ZoomIn
+                | Command::ZoomOut
+                | Command::ResetView
+                | Command::FitView
+                | Command::ZoomToSelection
+                | Command::NextBookmark
+                | Command::PrevBookmark
+                | Command::Pan
+        )
+    }
+}
+
+/// 키 조합 (정규화된 key + 수정자 비트, Meta는 Ctrl로 취급)
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Chord {
+    key: String,
+    modifiers: u32,
+}
+
+impl Chord {
+    /// "Mod+Shift+z" 형식 파싱
+    fn parse(s: &str) -> Result<Chord, String> {
+        // 마지막 키가 '+' 인 경우 ("Mod++")
+        let (mods, key) = match s.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if s == "+" => ("", "+"),
+            None => match s.rsplit_once('+') {
+                Some((mods, key)) => (mods, key),
+                None => ("", s),
+            },
+        };
+        if key.is_empty() {
+            return Err(format!("invalid chord: {}", s));
+        }
+
+        let mut modifiers = 0;
+        for m in mods.split('+').filter(|m| !m.is_empty()) {
+            modifiers |= match m.to_ascii_lowercase().as_str() {
+                "shift" => MOD_SHIFT,
+                "ctrl" | "control" | "cmd" | "meta" | "mod" => MOD_CTRL,
+                "alt" | "option" => MOD_ALT,
+                _ => return Err(format!("unknown modifier '{}' in {}", m, s)),
+            };
+        }
+
+        Ok(Chord {
+            key: normalize_key(key),
+            modifiers,
+        })
+    }
+
+    /// KeyboardEvent 값으로부터 생성
+    fn from_event(key: &str, modifiers: u32) -> Chord {
+        let mut modifiers = modifiers & (MOD_SHIFT | MOD_CTRL | MOD_ALT | MOD_META);
+        if modifiers & MOD_META != 0 {
+            modifiers = (modifiers & !MOD_META) | MOD_CTRL;
+        }
+        Chord {
+            key: normalize_key(key),
+            modifiers,
+        }
+    }
+
+    fn to_config_string(&self) -> String {
+        let mut s = String::new();
+        if self.modifiers & MOD_CTRL != 0 {
+            s.push_str("Mod+");
+        }
+        if self.modifiers & MOD_ALT != 0 {
+            s.push_str("Alt+");
+        }
+        if self.modifiers & MOD_SHIFT != 0 {
+            s.push_str("Shift+");
+        }
+        s.push_str(if self.key == " " { "Space" } else { &self.key });
+        s
+    }
+}
+
+/// 키 이름 정규화 (소문자, 별칭 통일)
+fn normalize_key(key: &str) -> String {
+    let lower = key.to_lowercase();
+    match lower.as_str() {
+        "space" | "spacebar" => " ".to_string(),
+        "esc" => "escape".to_string(),
+        "del" => "delete".to_string(),
+        "left" => "arrowleft".to_string(),
+        "right" => "arrowright".to_string(),
+        "up" => "arrowup".to_string(),
+        "down" => "arrowdown".to_string(),
+        _ => lower,
+    }
+}
+
+/// 키 조합 → 명령 매핑
+pub(crate) struct Keymap {
+    bindings: BTreeMap<Chord, Command>,
+}
+
+impl Keymap {
+    pub(crate) fn default_keymap() -> Keymap {
+        let mut bindings = BTreeMap::new();
+        for (chord, name) in DEFAULT_BINDINGS {
+            if let (Ok(chord), Some(cmd)) = (Chord::parse(chord), Command::from_name(name)) {
+                bindings.insert(chord, cmd);
+            }
+        }
+        Keymap { bindings }
+    }
+
+    /// 키 입력에 해당하는 명령 조회
+    fn lookup(&self, key: &str, modifiers: u32) -> Option<Command> {
+        let chord = Chord::from_event(key, modifiers);
+        if let Some(cmd) = self.bindings.get(&chord) {
+            return Some(*cmd);
+        }
+        // '+' 처럼 Shift가 필요한 기호 키는 Shift 없이 한 번 더 조회
+        let is_symbol = chord.key.chars().count() == 1
+            && !chord.key.chars().all(|c| c.is_alphanumeric() || c == ' ');
+        if is_symbol && chord.modifiers & MOD_SHIFT != 0 {
+            let relaxed = Chord {
+                key: chord.key,
+                modifiers: chord.modifiers & !MOD_SHIFT,
+            };
+            return self.bindings.get(&relaxed).copied();
+        }
+        None
+    }
+
+    /// JSON 설정 적용 ({"Mod+d": "duplicate", "Delete": null, ...}).
+    /// 기본 키맵 위에 덮어쓰며, null은 바인딩 해제.
+    /// 정규화 후 같은 키 조합에 서로 다른 명령이 지정되거나 (정렬 순 마지막 항목 적용)
+    /// 기존 바인딩을 다른 명령으로 덮어쓰면 충돌 목록으로 반환
+    fn apply_config(&mut self, json: &str) -> Result<Vec<String>, String> {
+        let config: BTreeMap<String, Option<String>> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let mut seen: BTreeMap<Chord, (String, Option<Command>)> = BTreeMap::new();
+        let mut conflicts = Vec::new();
+
+        for (chord_str, name) in &config {
+            let chord = Chord::parse(chord_str)?;
+            let cmd = match name {
+                Some(name) => Some(
+                    Command::from_name(name)
+                        .ok_or_else(|| format!("unknown command '{}' for {}", name, chord_str))?,
+                ),
+                None => None,
+            };
+            if let Some((prev_str, prev_cmd)) = seen.get(&chord) {
+                if *prev_cmd != cmd {
+                    conflicts.push(format!(
+                        "{} ({}) conflicts with {} ({})",
+                        chord_str,
+                        cmd.map(|c| c.name()).unwrap_or("null"),
+                        prev_str,
+                        prev_cmd.map(|c| c.name()).unwrap_or("null"),
+                    ));
+                }
+            }
+            seen.insert(chord, (chord_str.clone(), cmd));
+        }
+
+        for (chord, (chord_str, cmd)) in seen {
+            if let (Some(cmd), Some(prev)) = (cmd, self.bindings.get(&chord)) {
+                if cmd != *prev {
+                    conflicts.push(format!(
+                        "{} ({}) overrides default {}",
+                        chord_str,
+                        cmd.name(),
+                        prev.name(),
+                    ));
+                }
+            }
+            match cmd {
+                Some(cmd) => self.bindings.insert(chord, cmd),
+                None => self.bindings.remove(&chord),
+            };
+        }
+        Ok(conflicts)
+    }
+
+    /// 현재 키맵을 JSON으로
+    fn to_json(&self) -> String {
+        let map: BTreeMap<String, &str> = self
+            .bindings
+            .iter()
+            .map(|(chord, cmd)| (chord.to_config_string(), cmd.name()))
+            .collect();
+        serde_json::to_string(&map).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+impl CanvasInner {
+    /// 키 입력 처리. 명령이 실행되면 true (호스트는 preventDefault)
+    pub(crate) fn key_down(&mut self, key: &str, modifiers: u32) -> bool {
+        self.set_shape_modifiers(modifiers);
+        match self.keymap.lookup(key, modifiers) {
+            Some(cmd) => self.execute_command(cmd),
+            None => false,
+        }
+    }
+
+    /// 키 해제 처리 (팬 모드 등 누르고 있는 명령 종료)
+    pub(crate) fn key_up(&mut self, key: &str, modifiers: u32) -> bool {
+        self.set_shape_modifiers(modifiers);
+        // 수정자가 바뀌어도 해제되도록 수정자 없이도 조회
+        let cmd = self
+            .keymap
+            .lookup(key, modifiers)
+            .or_else(|| self.keymap.lookup(key, 0));
+        if cmd == Some(Command::Pan) && self.space_pan {
+            self.set_space_pan(false);
+            return true;
+        }
+        false
+    }
+
+    /// 명령 실행. 실제로 처리했으면 true (타임랩스 재생 중에는 보기 명령만)
+    pub(crate) fn execute_command(&mut self, cmd: Command) -> bool {
+        if self.replay.is_some() && !cmd.is_view() {
+            return false;
+        }
+        match cmd {
+            Command::ToolSelect => self.set_tool_mode("select"),
+            Command::ToolPen => self.set_tool_mode("pen"),
+            Command::ToolEraser => self.set_tool_mode("eraser"),
+            Command::ToolLine => self.set_tool_mode("line"),
+            Command::ToolRect => self.set_tool_mode("rect"),
+            Command::ToolCircle => self.set_tool_mode("circle"),
+            Command::Undo => {
+                if self.undo_stack.is_empty() {
+                    return false;
+                }
+                self.undo();
+            }
+            Command::Redo => {
+                if self.redo_stack.is_empty() {
+                    return false;
+                }
+                self.redo();
+            }
+            Command::Copy => {
+                if !self.has_selection() {
+                    return false;
+                }
+                self.copy_selected();
+            }
+            Command::Paste => {
+                if self.clipboard.is_empty() {
+                    return false;
+                }
+                self.paste();
+            }
+            Command::Duplicate => {
+                if !self.has_selection() {
+                    return false;
+                }
+                self.duplicate_selected();
+            }
+            Command::Delete => {
+                if !self.has_selection() {
+                    return false;
+                }
+                self.delete_selected();
+            }
+            Command::SelectAll => {
+                if self.tool_mode != ToolMode::Select {
+                    return false;
+                }
+                self.select_all();
+            }
+            Command::Deselect => {
+                // 진행 중인 드래그/그리기가 있으면 확정 없이 취소
+                if self.interaction != Interaction::Idle {
+                    self.cancel_interaction();
+                    return true;
+                }
+                // 선택이 없으면 그룹 한 단계 밖으로
+                if !self.has_selection() {
+                    return self.exit_group();
+                }
+                self.deselect_all();
+            }
+            Command::NudgeLeft => return self.nudge(-1.0, 0.0, false),
+            Command::NudgeRight => return self.nudge(1.0, 0.0, false),
+            Command::NudgeUp => return self.nudge(0.0, -1.0, false),
+            Command::NudgeDown => return self.nudge(0.0, 1.0, false),
+            Command::NudgeLeftLarge => return self.nudge(-1.0, 0.0, true),
+            Command::NudgeRightLarge => return self.nudge(1.0, 0.0, true),
+            Command::NudgeUpLarge => return self.nudge(0.0, -1.0, true),
+            Command::NudgeDownLarge => return self.nudge(0.0, 1.0, true),
+            Command::ZoomIn => self.zoom_step(true),
+            Command::ZoomOut => self.zoom_step(false),
+            Command::ResetView => self.reset_view(),
+            Command::FitView => self.fit_to_view(),
+            Command::ZoomToSelection => return self.zoom_to_selection(),
+            Command::NextBookmark => return self.step_bookmark(true).is_some(),
+            Command::PrevBookmark => return self.step_bookmark(false).is_some(),
+            Command::AlignLeft => return self.align_selected("left", "selection") == Ok(true),
+            Command::AlignCenter => return self.align_selected("center", "selection") == Ok(true),
+            Command::AlignRight => return self.align_selected("right", "selection") == Ok(true),
+            Command::AlignTop => return self.align_selected("top", "selection") == Ok(true),
+            Command::AlignMiddle => return self.align_selected("middle", "selection") == Ok(true),
+            Command::AlignBottom => return self.align_selected("bottom", "selection") == Ok(true),
+            Command::DistributeHorizontal => {
+                return self.distribute_selected("horizontal") == Ok(true)
+            }
+            Command::DistributeVertical => return self.distribute_selected("vertical") == Ok(true),
+            Command::FlipHorizontal => return self.flip_selected("horizontal") == Ok(true),
+            Command::FlipVertical => return self.flip_selected("vertical") == Ok(true),
+            Command::BringToFront => return self.reorder_selected("front") == Ok(true),
+            Command::SendToBack => return self.reorder_selected("back") == Ok(true),
+            Command::BringForward => return self.reorder_selected("forward") == Ok(true),
+            Command::SendBackward => return self.reorder_selected("backward") == Ok(true),
+            Command::Group => return self.group_selected(),
+            Command::Ungroup => return self.ungroup_selected(),
+            Command::LockSelected => return self.lock_selected(),
+            Command::UnlockAll => return self.unlock_all(),
+            Command::HideSelected => return self.hide_selected(),
+            Command::ShowAll => return self.show_all(),
+            Command::Pan => {
+                if !self.space_pan {
+                    self.set_space_pan(true);
+                }
+            }
+        }
+        true
+    }
+
+    /// 키맵 설정 적용 → 충돌 목록 JSON 배열
+    pub(crate) fn set_keymap(&mut self, json: &str) -> Result<String, String> {
+        let mut keymap = Keymap::default_keymap();
+        let conflicts = keymap.apply_config(json)?;
+        self.keymap = keymap;
+        Ok(serde_json::to_string(&conflicts).unwrap_or_else(|_| "[]".to_string()))
+    }
+
+    /// 현재 키맵 JSON
+    pub(crate) fn get_keymap(&self) -> String {
+        self.keymap.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(s: &str) -> Chord {
+        Chord::parse(s).unwrap()
+    }
+
+    #[test]
+    fn chord_parse_normalizes_modifiers_and_keys() {
+        assert!(chord("Mod+Shift+Z") == chord("shift+ctrl+z"));
+        assert!(chord("Cmd+z") == chord("Control+Z"));
+        assert!(chord("Esc") == chord("Escape"));
+        assert!(chord("Mod++") == Chord::from_event("+", MOD_CTRL));
+        assert_eq!(
+            chord("Shift+Option+Cmd+Space").to_config_string(),
+            "Mod+Alt+Shift+Space"
+        );
+
+        assert!(Chord::parse("Hyper+z").is_err());
+        assert!(Chord::parse("Mod+").is_err());
+    }
+
+    #[test]
+    fn lookup_treats_meta_as_ctrl_and_relaxes_shift_for_symbols() {
+        let keymap = Keymap::default_keymap();
+        assert_eq!(keymap.lookup("z", MOD_META), Some(Command::Undo));
+        assert_eq!(
+            keymap.lookup("Z", MOD_CTRL | MOD_SHIFT),
+            Some(Command::Redo)
+        );
+        // '+'는 보통 Shift와 함께 입력됨
+        assert_eq!(keymap.lookup("+", MOD_SHIFT), Some(Command::ZoomIn));
+        // 문자 키는 Shift를 무시하지 않음
+        assert_eq!(keymap.lookup("p", MOD_SHIFT), None);
+    }
+
+    #[test]
+    fn config_reports_conflicts_with_itself_and_defaults() {
+        let mut keymap = Keymap::default_keymap();
+        let conflicts = keymap
+            .apply_config(r#"{"Ctrl+k": "group", "Mod+K": "ungroup", "Mod+d": "duplicate", "Mod+g": "flip_vertical", "Delete": null}"#)
+            .unwrap();
+
+        assert_eq!(
+            conflicts,
+            vec![
+                "Mod+K (ungroup) conflicts with Ctrl+k (group)",
+                "Mod+g (flip_vertical) overrides default group",
+            ]
+        );
+        assert_eq!(keymap.lookup("k", MOD_CTRL), Some(Command::Ungroup));
+        assert_eq!(keymap.lookup("g", MOD_CTRL), Some(Command::FlipVertical));
+        assert_eq!(keymap.lookup("Delete", 0), None);
+
+        assert!(keymap.apply_config(r#"{"x": "explode"}"#).is_err());
+    }
+
+    #[test]
+    fn commands_report_whether_anything_happened() {
+        let mut c = CanvasInner::for_test();
+        assert!(!c.key_down("z", MOD_CTRL));
+        assert!(!c.key_down("y", MOD_CTRL));
+        assert!(!c.key_down("v", MOD_CTRL));
+    }
+
+    #[test]
+    fn escape_cancels_stroke_in_progress() {
+        let mut c = CanvasInner::for_test();
+        c.set_tool_mode("pen");
+        c.pointer_down(100.0, 100.0, 1, 0, "mouse", 1);
+        c.pointer_move(150.0, 100.0, 1, 0, "mouse", 1);
+        assert!(c.key_down("Escape", 0));
+        c.pointer_up(150.0, 100.0, 0, 0, "mouse", 1);
+
+        assert!(c.interaction == Interaction::Idle);
+        assert!(c.elements.is_empty() && c.undo_stack.is_empty());
+    }
+}
@@ -0,0 +1,169 @@
+use crate::models::Point;
+use crate::region::{Region, RegionMode};
+use crate::CanvasInner;
+
+// ===== 올가미 (자유 곡선) 선택 =====
+
+/// 올가미 점을 추가하는 최소 이동 거리 (스크린 px)
+const LASSO_MIN_STEP_PX: f64 = 2.0;
+
+impl CanvasInner {
+    /// 올가미 선택 시작 (스크린 좌표)
+    pub(crate) fn start_lasso(&mut self, x: f64, y: f64) {
+        self.is_lasso = true;
+        self.lasso_points = vec![Point { x, y }];
+    }
+
+    /// 올가미 드래그 중 — 점 추가 (가까운 점은 건너뜀)
+    pub(crate) fn update_lasso(&mut self, x: f64, y: f64) {
+        if !self.is_lasso {
+            return;
+        }
+        let far_enough = self
+            .lasso_points
+            .last()
+            .is_none_or(|p| (x - p.x).hypot(y - p.y) >= LASSO_MIN_STEP_PX);
+        if far_enough {
+            self.lasso_points.push(Point { x, y });
+            self.needs_render = true;
+        }
+    }
+
+    /// 올가미 선택 확정 — 닫힌 다각형으로 요소 판정 (lasso_mode 기준)
+    pub(crate) fn finish_lasso(&mut self, shift: bool) {
+        if !self.is_lasso {
+            return;
+        }
+        self.is_lasso = false;
+
+        let points: Vec<Point> = std::mem::take(&mut self.lasso_points)
+            .into_iter()
+            .map(|p| Point {
+                x: self.screen_to_world_x(p.x),
+                y: self.screen_to_world_y(p.y),
+            })
+            .collect();
+
+        if !shift {
+            self.selected_ids.clear();
+        }
+        self.last_duplicate = None;
+
+        if let Some(region) = Region::polygon(points) {
+            let hits: Vec<u32> = self
+                .pickable()
+                .into_iter()
+                .filter(|e| region.selects(e, self.lasso_mode))
+                .map(|e| e.id)
+                .collect();
+            self.selected_ids.extend(hits);
+        }
+
+        self.needs_render = true;
+    }
+
+    /// 선택 도구로 빈 곳을 드래그할 때 러버밴드 대신 올가미 사용
+    pub(crate) fn set_lasso_select(&mut self, enabled: bool) {
+        self.lasso_select = enabled;
+    }
+
+    /// 올가미 판정 방식 (mode: "contain" | "intersect")
+    pub(crate) fn set_lasso_mode(&mut self, mode: &str) -> Result<(), String> {
+        self.lasso_mode =
+            RegionMode::parse(mode).ok_or_else(|| format!("unknown lasso mode: {}", mode))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+    use crate::CanvasInner;
+
+    fn rect(id: u32, x: f64, y: f64) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect {
+                x,
+                y,
+                w: 20.0,
+                h: 20.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    /// 2배 줌: 1은 (10, 10), 2는 (50, 10), 3은 (100, 100)에 20×20 사각형
+    fn canvas() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.jump_camera(2.0, 0.0, 0.0);
+        c.elements = vec![
+            rect(1, 10.0, 10.0),
+            rect(2, 50.0, 10.0),
+            rect(3, 100.0, 100.0),
+        ];
+        c
+    }
+
+    /// 스크린 (0, 0)–(120, 80) 사각형 올가미 = 월드 (0, 0)–(60, 40)
+    fn lasso(c: &mut CanvasInner, shift: bool) {
+        c.start_lasso(0.0, 0.0);
+        c.update_lasso(120.0, 0.0);
+        c.update_lasso(120.0, 80.0);
+        c.update_lasso(120.5, 80.5);
+        c.update_lasso(0.0, 80.0);
+        c.finish_lasso(shift);
+    }
+
+    fn selected(c: &CanvasInner) -> Vec<u32> {
+        let mut ids: Vec<u32> = c.selected_ids.iter().copied().collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn lasso_is_converted_to_world_coordinates() {
+        let mut c = canvas();
+        c.start_lasso(0.0, 0.0);
+        c.update_lasso(120.0, 0.0);
+        // 2px 미만 이동은 건너뜀
+        c.update_lasso(121.0, 0.5);
+        assert_eq!(c.lasso_points.len(), 2);
+
+        let mut c = canvas();
+        lasso(&mut c, false);
+        // 월드 좌표 기준이면 1만 완전히 포함 (스크린 좌표로 판정하면 2도 포함됨)
+        assert_eq!(selected(&c), vec![1]);
+        assert!(!c.is_lasso && c.lasso_points.is_empty());
+    }
+
+    #[test]
+    fn shift_extends_selection() {
+        let mut c = canvas();
+        c.selected_ids.insert(3);
+        lasso(&mut c, true);
+        assert_eq!(selected(&c), vec![1, 3]);
+
+        lasso(&mut c, false);
+        assert_eq!(selected(&c), vec![1]);
+    }
+
+    #[test]
+    fn intersect_mode_selects_crossed_elements() {
+        let mut c = canvas();
+        assert!(c.set_lasso_mode("touch").is_err());
+        c.set_lasso_mode("intersect").unwrap();
+        lasso(&mut c, false);
+        assert_eq!(selected(&c), vec![1, 2]);
+
+        c.set_lasso_mode("contain").unwrap();
+        lasso(&mut c, false);
+        assert_eq!(selected(&c), vec![1]);
+    }
+}
@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+
+use crate::crdt::SITE_SHIFT;
+use crate::models::Element;
+use crate::oplog::LogOp;
+use crate::{Action, CanvasInner};
+
+// ===== 레이어 =====
+
+/// 기본 레이어 ID (레이어 정보가 없는 요소가 속하는 곳)
+pub(crate) const DEFAULT_LAYER_ID: u32 = 0;
+
+/// 레이어 (목록 순서가 그리기 순서, 뒤쪽이 위)
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Layer {
+    pub(crate) id: u32,
+    pub(crate) name: String,
+    pub(crate) visible: bool,
+    pub(crate) locked: bool,
+    pub(crate) opacity: f64,
+}
+
+impl Layer {
+    fn new(id: u32, name: &str) -> Layer {
+        Layer {
+            id,
+            name: name.to_string(),
+            visible: true,
+            locked: false,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// 새 문서의 레이어 목록
+pub(crate) fn default_layers() -> Vec<Layer> {
+    vec![Layer::new(DEFAULT_LAYER_ID, "Layer 1")]
+}
+
+/// 요소의 레이어 ID → 레이어 인덱스 (없는 레이어면 맨 아래 레이어)
+pub(crate) fn layer_index(layers: &[Layer], id: u32) -> usize {
+    layers.iter().position(|l| l.id == id).unwrap_or(0)
+}
+
+impl CanvasInner {
+    /// 요소가 속한 레이어 (최상위 요소 기준)
+    fn layer_of(&self, elem: &Element) -> Option<&Layer> {
+        self.layers.get(layer_index(&self.layers, elem.layer))
+    }
+
+    /// 그려지는 요소인지 (숨긴 요소/레이어 제외). 그룹 내부에서는 레이어 무시
+    pub(crate) fn is_rendered(&self, elem: &Element) -> bool {
+        if elem.hidden {
+            return false;
+        }
+        !self.entered_groups.is_empty() || self.layer_of(elem).is_none_or(|l| l.visible)
+    }
+
+    /// 선택할 수 있는 요소인지 (숨김/잠금 요소와 레이어 제외)
+    pub(crate) fn is_interactive(&self, elem: &Element) -> bool {
+        if elem.hidden || elem.locked {
+            return false;
+        }
+        !self.entered_groups.is_empty()
+            || self.layer_of(elem).is_none_or(|l| l.visible && !l.locked)
+    }
+
+    /// 현재 범위에서 선택 가능한 요소 (그리기 순서: 레이어 순 → 요소 순)
+    pub(crate) fn pickable(&self) -> Vec<&Element> {
+        let mut items: Vec<&Element> = self
+            .scope()
+            .iter()
+            .filter(|e| self.is_interactive(e))
+            .collect();
+        if self.entered_groups.is_empty() {
+            items.sort_by_key(|e| layer_index(&self.layers, e.layer));
+        }
+        items
+    }
+
+    /// 레이어 목록 변경을 로그에 기록하고 다시 그림
+    fn layers_changed(&mut self) {
+        self.log_op(LogOp::SetLayers {
+            layers: self.layers.clone(),
+        });
+        self.needs_render = true;
+    }
+
+    /// 숨김/잠금된 요소를 선택에서 제외
+    pub(crate) fn drop_uninteractive_selection(&mut self) {
+        let dropped: Vec<u32> = self
+            .scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id) && !self.is_interactive(e))
+            .map(|e| e.id)
+            .collect();
+        for id in dropped {
+            self.selected_ids.remove(&id);
+        }
+    }
+
+    /// 맨 위에 새 레이어를 추가하고 활성화 → 새 레이어 ID
+    pub(crate) fn add_layer(&mut self, name: &str) -> u32 {
+        let id = self.next_layer_id;
+        self.next_layer_id += 1;
+        self.layers.push(Layer::new(id, name));
+        self.active_layer = id;
+        self.layers_changed();
+        id
+    }
+
+    /// 레이어와 그 요소 삭제 (undo 시 레이어와 요소 모두 복원). 마지막 레이어는 삭제 불가
+    pub(crate) fn remove_layer(&mut self, id: u32) -> bool {
+        if self.layers.len() <= 1 {
+            return false;
+        }
+        let Some(index) = self.layers.iter().position(|l| l.id == id) else {
+            return false;
+        };
+
+        let deleted: Vec<(usize, Element)> = self
+            .elements
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.layer == id)
+            .map(|(i, e)| (i, e.clone()))
+            .collect();
+        for (_, e) in &deleted {
+            self.selected_ids.remove(&e.id);
+        }
+        let action = Action::RemoveLayer {
+            layer: self.layers[index].clone(),
+            index,
+            elements: deleted,
+        };
+        action.apply(&mut self.elements);
+        self.push_action(action.clone());
+        self.sync_layer_action(&action, true);
+        true
+    }
+
+    /// 레이어 삭제 액션의 레이어 목록 부분 반영 (applied: 적용/redo면 true, undo면 false).
+    /// 요소 목록은 Action::apply/revert가, 레이어 목록은 여기서 처리
+    pub(crate) fn sync_layer_action(&mut self, action: &Action, applied: bool) {
+        let Action::RemoveLayer { layer, index, .. } = action else {
+            return;
+        };
+        let position = self.layers.iter().position(|l| l.id == layer.id);
+        match (applied, position) {
+            (true, Some(i)) if self.layers.len() > 1 => {
+                self.layers.remove(i);
+                if self.active_layer == layer.id {
+                    self.active_layer = self.layers[i.saturating_sub(1)].id;
+                }
+            }
+            (false, None) => {
+                let at = (*index).min(self.layers.len());
+                self.layers.insert(at, layer.clone());
+            }
+            _ => return,
+        }
+        self.entered_groups.clear();
+        self.layers_changed();
+    }
+
+    /// 레이어 속성 변경 공통 처리
+    fn update_layer(&mut self, id: u32, f: impl FnOnce(&mut Layer)) -> bool {
+        let Some(layer) = self.layers.iter_mut().find(|l| l.id == id) else {
+            return false;
+        };
+        f(layer);
+        self.drop_uninteractive_selection();
+        self.layers_changed();
+        true
+    }
+
+    pub(crate) fn rename_layer(&mut self, id: u32, name: &str) -> bool {
+        self.update_layer(id, |l| l.name = name.to_string())
+    }
+
+    pub(crate) fn set_layer_visible(&mut self, id: u32, visible: bool) -> bool {
+        self.update_layer(id, |l| l.visible = visible)
+    }
+
+    pub(crate) fn set_layer_locked(&mut self, id: u32, locked: bool) -> bool {
+        self.update_layer(id, |l| l.locked = locked)
+    }
+
+    pub(crate) fn set_layer_opacity(&mut self, id: u32, opacity: f64) -> bool {
+        let opacity = if opacity.is_nan() {
+            1.0
+        } else {
+            opacity.clamp(0.0, 1.0)
+        };
+        self.update_layer(id, |l| l.opacity = opacity)
+    }
+
+    /// 레이어 순서 변경 (index: 0이 맨 아래)
+    pub(crate) fn move_layer(&mut self, id: u32, index: usize) -> bool {
+        let Some(from) = self.layers.iter().position(|l| l.id == id) else {
+            return false;
+        };
+        let layer = self.layers.remove(from);
+        let to = index.min(self.layers.len());
+        self.layers.insert(to, layer);
+        self.layers_changed();
+        true
+    }
+
+    /// 새 요소가 들어갈 레이어 지정
+    pub(crate) fn set_active_layer(&mut self, id: u32) -> bool {
+        if !self.layers.iter().any(|l| l.id == id) {
+            return false;
+        }
+        self.active_layer = id;
+        true
+    }
+
+    /// 선택된 최상위 요소를 다른 레이어로 이동 (undo 가능)
+    pub(crate) fn move_selection_to_layer(&mut self, id: u32) -> bool {
+        if !self.layers.iter().any(|l| l.id == id) {
+            return false;
+        }
+        let changes: Vec<(u32, u32, u32)> = self
+            .elements
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id) && e.layer != id)
+            .map(|e| (e.id, e.layer, id))
+            .collect();
+        if changes.is_empty() {
+            return false;
+        }
+        let action = Action::SetLayer { changes };
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.drop_uninteractive_selection();
+        self.needs_render = true;
+        true
+    }
+
+    /// 레이어 목록 JSON ({"active": id, "layers": [...]}, 아래 → 위 순)
+    pub(crate) fn get_layers(&self) -> String {
+        serde_json::json!({
+            "active": self.active_layer,
+            "layers": self.layers,
+        })
+        .to_string()
+    }
+
+    /// 이미 쓰인 레이어 ID(used) 이후부터 새 ID 발급.
+    /// 협업 중 다른 피어의 ID 구간으로 넘어가지 않도록 같은 사이트 구간의 ID만 반영
+    pub(crate) fn reserve_layer_id(&mut self, used: u32) {
+        if used >> SITE_SHIFT == self.next_layer_id >> SITE_SHIFT {
+            self.next_layer_id = self.next_layer_id.max(used + 1);
+        }
+    }
+
+    /// 레이어 목록 교체 (로그 가져오기 등). 활성 레이어가 없으면 맨 위로
+    pub(crate) fn restore_layers(&mut self, layers: Vec<Layer>) {
+        self.layers = if layers.is_empty() {
+            default_layers()
+        } else {
+            layers
+        };
+        if let Some(max) = self.layers.iter().map(|l| l.id).max() {
+            self.reserve_layer_id(max);
+        }
+        if !self.layers.iter().any(|l| l.id == self.active_layer) {
+            self.active_layer = self.layers[self.layers.len() - 1].id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+    use crate::CanvasInner;
+
+    fn dot(id: u32, layer: u32) -> Element {
+        Element {
+            id,
+            shape: Shape::Circle {
+                cx: 0.0,
+                cy: 0.0,
+                r: 5.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn layer_ids(c: &CanvasInner) -> Vec<u32> {
+        c.layers.iter().map(|l| l.id).collect()
+    }
+
+    fn element_ids(c: &CanvasInner) -> Vec<u32> {
+        c.elements.iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn remove_layer_is_undoable() {
+        let mut c = CanvasInner::for_test();
+        let top = c.add_layer("top");
+        let mid = c.add_layer("mid");
+        c.move_layer(mid, 1);
+        c.elements = vec![dot(1, 0), dot(2, mid), dot(3, top), dot(4, mid)];
+        c.next_id = 5;
+        c.selected_ids.insert(2);
+        c.active_layer = mid;
+        let before = layer_ids(&c);
+
+        assert!(c.remove_layer(mid));
+        assert!(!c.layers.iter().any(|l| l.id == mid));
+        assert_eq!(element_ids(&c), vec![1, 3]);
+        assert!(c.selected_ids.is_empty());
+        assert_ne!(c.active_layer, mid);
+
+        c.undo();
+        assert_eq!(layer_ids(&c), before);
+        assert_eq!(element_ids(&c), vec![1, 2, 3, 4]);
+
+        c.redo();
+        assert!(!c.layers.iter().any(|l| l.id == mid));
+        assert_eq!(element_ids(&c), vec![1, 3]);
+    }
+
+    #[test]
+    fn removing_empty_layer_is_undoable() {
+        let mut c = CanvasInner::for_test();
+        let id = c.add_layer("empty");
+        let before = layer_ids(&c);
+
+        assert!(c.remove_layer(id));
+        assert_eq!(c.layers.len(), before.len() - 1);
+        c.undo();
+        assert_eq!(layer_ids(&c), before);
+    }
+
+    fn layer_names(c: &CanvasInner) -> Vec<String> {
+        c.layers
+            .iter()
+            .map(|l| format!("{}:{}", l.id, l.name))
+            .collect()
+    }
+
+    #[test]
+    fn removed_layer_id_is_not_reused() {
+        let mut c = CanvasInner::for_test();
+        let a = c.add_layer("a");
+        c.elements = vec![dot(1, a)];
+        c.next_id = 2;
+
+        assert!(c.remove_layer(a));
+        let b = c.add_layer("b");
+        assert_ne!(a, b);
+
+        c.undo();
+        assert_eq!(layer_names(&c), vec!["0:Layer 1", "1:a", "2:b"]);
+        assert_eq!(c.elements[0].layer, a);
+    }
+
+    #[test]
+    fn remove_layer_keeps_elements_of_unknown_layers() {
+        // 다른 피어의 레이어를 가리키는 요소는 맨 아래 레이어에 그려지지만 그 레이어 소속은 아님
+        let mut c = CanvasInner::for_test();
+        let top = c.add_layer("top");
+        c.elements = vec![dot(1, 0), dot(2, 99), dot(3, top)];
+        c.next_id = 4;
+
+        assert!(c.remove_layer(0));
+        assert_eq!(element_ids(&c), vec![2, 3]);
+    }
+
+    #[test]
+    fn last_layer_cannot_be_removed() {
+        let mut c = CanvasInner::for_test();
+        let id = c.layers[0].id;
+        assert!(!c.remove_layer(id));
+        assert!(c.undo_stack.is_empty());
+    }
+}
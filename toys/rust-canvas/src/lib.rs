@@ -1,9 +1,31 @@
+mod align;
+mod camera;
+mod clipboard;
+mod constraint;
+mod crdt;
+mod flags;
+mod flip;
+mod gesture;
+mod group;
+mod grid;
+mod input;
+mod keymap;
+mod lasso;
+mod layers;
 mod models;
+mod nudge;
+mod oplog;
+mod presence;
+mod query;
+mod region;
 mod rendering;
 mod selection;
+mod snap;
 mod svg_export;
+mod svg_import;
+mod zorder;
 
-use std::cell::{Cell, RefCell};
+use std::cell::{Cell, RefCell, RefMut};
 use std::collections::HashSet;
 use std::rc::Rc;
 use wasm_bindgen::closure::Closure;
@@ -11,11 +33,37 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use js_sys::Array as JsArray;
-
-use crate::models::{BoundingBox, Element, Point, Shape, Style};
+use serde::{Deserialize, Serialize};
+
+use crate::camera::{Bookmark, CameraAnimation, CameraConfig, DEFAULT_CAMERA_DURATION_MS};
+use crate::clipboard::CLIPBOARD_MIME;
+use crate::constraint::DEFAULT_ANGLE_STEP_DEG;
+use crate::crdt::Document;
+use crate::flags::{set_flag, ElementFlag};
+use crate::gesture::Gesture;
+use crate::group::{apply_group, apply_ungroup, group_children_mut, revert_group, revert_ungroup};
+use crate::grid::GridConfig;
+use crate::input::Interaction;
+use crate::keymap::Keymap;
+use crate::layers::{default_layers, Layer, DEFAULT_LAYER_ID};
+use crate::models::{combined_bounds, union_bounds, BoundingBox, Element, Point, Shape, Style};
+use crate::nudge::NudgeRun;
+use crate::oplog::{LogEntry, LogOp, Replay};
+use crate::presence::{Peer, DEFAULT_PEER_TIMEOUT_MS};
+use crate::region::{Region, RegionMode, RubberBandMode};
+use crate::snap::Guide;
+use crate::zorder::reorder;
 
 // ===== 내부 타입 =====
 
+/// 마지막 복제 기록 (복제본을 옮긴 뒤 다시 복제하면 같은 변위를 반복)
+pub(crate) struct DuplicateStep {
+    /// 복제로 생긴 요소 ID
+    ids: HashSet<u32>,
+    /// 복제 시점 원본 선택 영역의 좌상단
+    origin: (f64, f64),
+}
+
 /// 도구 모드
 #[derive(Clone, Copy, PartialEq)]
 pub(crate) enum ToolMode {
@@ -28,7 +76,8 @@ pub(crate) enum ToolMode {
 }
 
 /// Undo/Redo 가능한 액션
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "kind")]
 enum Action {
     /// 요소 추가 (그리기/도형)
     AddElement { element: Element },
@@ -36,12 +85,173 @@ enum Action {
     DeleteElements { elements: Vec<(usize, Element)> },
     /// 요소 이동
     MoveElements { ids: Vec<u32>, dx: f64, dy: f64 },
+    /// 뒤집기 (horizontal: 좌우, 아니면 상하 / center: 기준축 좌표, 자기 역연산)
+    Flip { ids: Vec<u32>, horizontal: bool, center: f64 },
+    /// 요소별로 다른 이동 (정렬/분배)
+    TranslateEach { moves: Vec<(u32, f64, f64)> },
+    /// 그리기 순서 변경 (변경 전/후 ID 순서)
+    ReorderElements { before: Vec<u32>, after: Vec<u32> },
+    /// 그룹으로 묶기 (멤버 원래 인덱스, 그룹, 그룹 삽입 위치)
+    GroupElements { members: Vec<(usize, Element)>, group: Element, index: usize },
+    /// 그룹 해제 (그룹 원래 인덱스 오름차순)
+    UngroupElements { groups: Vec<(usize, Element)> },
+    /// 레이어 이동 (ID, 이전 레이어, 새 레이어)
+    SetLayer { changes: Vec<(u32, u32, u32)> },
+    /// 레이어 삭제 (레이어 원래 위치와 함께 삭제된 요소)
+    RemoveLayer { layer: Layer, index: usize, elements: Vec<(usize, Element)> },
+    /// 잠금/숨김 설정 (값이 바뀐 요소만 포함)
+    SetFlag { flag: ElementFlag, ids: Vec<u32>, value: bool },
+    /// 그룹 내부(자식 목록)에 적용되는 액션
+    InGroup { group: u32, action: Box<Action> },
     /// 붙여넣기
     PasteElements { elements: Vec<Element> },
     /// 전체 지우기
     ClearAll { elements: Vec<Element> },
 }
 
+impl Action {
+    /// 액션을 요소 목록에 적용 (Redo / 로그 재생)
+    fn apply(&self, elements: &mut Vec<Element>) {
+        match self {
+            Action::AddElement { element } => {
+                elements.push(element.clone());
+            }
+            Action::DeleteElements { elements: deleted }
+            | Action::RemoveLayer { elements: deleted, .. } => {
+                let ids: HashSet<u32> = deleted.iter().map(|(_, e)| e.id).collect();
+                elements.retain(|e| !ids.contains(&e.id));
+            }
+            Action::MoveElements { ids, dx, dy } => {
+                for elem in elements.iter_mut() {
+                    if ids.contains(&elem.id) {
+                        elem.translate(*dx, *dy);
+                    }
+                }
+            }
+            Action::Flip { ids, horizontal, center } => {
+                for elem in elements.iter_mut() {
+                    if ids.contains(&elem.id) {
+                        elem.flip(*horizontal, *center);
+                    }
+                }
+            }
+            Action::TranslateEach { moves } => {
+                for (id, dx, dy) in moves {
+                    if let Some(elem) = elements.iter_mut().find(|e| e.id == *id) {
+                        elem.translate(*dx, *dy);
+                    }
+                }
+            }
+            Action::ReorderElements { after, .. } => reorder(elements, after),
+            Action::GroupElements { members, group, index } => {
+                apply_group(elements, members, group, *index);
+            }
+            Action::UngroupElements { groups } => apply_ungroup(elements, groups),
+            Action::SetLayer { changes } => {
+                for (id, _, to) in changes {
+                    if let Some(elem) = elements.iter_mut().find(|e| e.id == *id) {
+                        elem.layer = *to;
+                    }
+                }
+            }
+            Action::SetFlag { flag, ids, value } => set_flag(elements, *flag, ids, *value),
+            Action::InGroup { group, action } => {
+                if let Some(children) = group_children_mut(elements, *group) {
+                    action.apply(children);
+                }
+            }
+            Action::PasteElements { elements: pasted } => {
+                for e in pasted {
+                    elements.push(e.clone());
+                }
+            }
+            Action::ClearAll { .. } => {
+                elements.clear();
+            }
+        }
+    }
+
+    /// 액션을 되돌림 (Undo)
+    fn revert(&self, elements: &mut Vec<Element>) {
+        match self {
+            Action::AddElement { element } => {
+                elements.retain(|e| e.id != element.id);
+            }
+            Action::DeleteElements { elements: deleted }
+            | Action::RemoveLayer { elements: deleted, .. } => {
+                for (idx, e) in deleted {
+                    let insert_at = (*idx).min(elements.len());
+                    elements.insert(insert_at, e.clone());
+                }
+            }
+            Action::MoveElements { ids, dx, dy } => {
+                for elem in elements.iter_mut() {
+                    if ids.contains(&elem.id) {
+                        elem.translate(-dx, -dy);
+                    }
+                }
+            }
+            // 같은 축으로 한 번 더 뒤집으면 원래대로
+            Action::Flip { .. } => self.apply(elements),
+            Action::TranslateEach { moves } => {
+                for (id, dx, dy) in moves {
+                    if let Some(elem) = elements.iter_mut().find(|e| e.id == *id) {
+                        elem.translate(-dx, -dy);
+                    }
+                }
+            }
+            Action::ReorderElements { before, .. } => reorder(elements, before),
+            Action::GroupElements { members, group, .. } => revert_group(elements, members, group),
+            Action::UngroupElements { groups } => revert_ungroup(elements, groups),
+            Action::SetLayer { changes } => {
+                for (id, from, _) in changes {
+                    if let Some(elem) = elements.iter_mut().find(|e| e.id == *id) {
+                        elem.layer = *from;
+                    }
+                }
+            }
+            Action::SetFlag { flag, ids, value } => set_flag(elements, *flag, ids, !value),
+            Action::InGroup { group, action } => {
+                if let Some(children) = group_children_mut(elements, *group) {
+                    action.revert(children);
+                }
+            }
+            Action::PasteElements { elements: pasted } => {
+                let ids: HashSet<u32> = pasted.iter().map(|e| e.id).collect();
+                elements.retain(|e| !ids.contains(&e.id));
+            }
+            Action::ClearAll { elements: cleared } => {
+                *elements = cleared.clone();
+            }
+        }
+    }
+
+    /// 액션이 담고 있는 가장 큰 요소 ID (그룹 내부 포함)
+    fn max_id(&self) -> Option<u32> {
+        match self {
+            Action::AddElement { element } => Some(element.max_id()),
+            Action::DeleteElements { elements }
+            | Action::RemoveLayer { elements, .. }
+            | Action::UngroupElements { groups: elements } => {
+                elements.iter().map(|(_, e)| e.max_id()).max()
+            }
+            Action::MoveElements { ids, .. }
+            | Action::Flip { ids, .. }
+            | Action::SetFlag { ids, .. } => {
+                ids.iter().copied().max()
+            }
+            Action::TranslateEach { moves } => moves.iter().map(|(id, _, _)| *id).max(),
+            Action::ReorderElements { before, .. } => before.iter().copied().max(),
+            Action::PasteElements { elements } | Action::ClearAll { elements } => {
+                elements.iter().map(|e| e.max_id()).max()
+            }
+            Action::GroupElements { group, .. } => Some(group.max_id()),
+            Action::SetLayer { changes } => changes.iter().map(|(id, _, _)| *id).max(),
+            Action::InGroup { group, action } => action.max_id().max(Some(*group)),
+        }
+    }
+}
+
 // ===== CanvasInner: 모든 상태 및 로직 (JS에 직접 노출되지 않음) =====
 
 pub(crate) struct CanvasInner {
@@ -62,11 +272,20 @@ pub(crate) struct CanvasInner {
     pub(crate) cursor_x: f64,
     pub(crate) cursor_y: f64,
     pub(crate) show_cursor: bool,
+    /// 마지막 커서 위치가 유효한지 (캔버스 위, 도구와 무관)
+    pub(crate) cursor_inside: bool,
 
     // 선택 상태
     pub(crate) tool_mode: ToolMode,
     pub(crate) selected_ids: HashSet<u32>,
+    /// 처음 선택한 요소 (정렬 기준)
+    pub(crate) selection_anchor: Option<u32>,
+    /// 더블클릭으로 들어간 그룹 경로 (마지막이 현재 편집 범위)
+    pub(crate) entered_groups: Vec<u32>,
     pub(crate) clipboard: Vec<Element>,
+    pub(crate) last_duplicate: Option<DuplicateStep>,
+    /// 직전 키보드 이동 (연속 이동 병합용, 다른 액션이 기록되면 해제)
+    pub(crate) last_nudge: Option<NudgeRun>,
 
     // 드래그 이동 상태
     pub(crate) is_moving: bool,
@@ -74,6 +293,8 @@ pub(crate) struct CanvasInner {
     pub(crate) move_start_y: f64,
     pub(crate) move_total_dx: f64,
     pub(crate) move_total_dy: f64,
+    /// 이동 시작 시 선택 영역 (스냅 기준)
+    pub(crate) move_origin: Option<BoundingBox>,
 
     // 러버밴드 (드래그 영역) 선택 상태
     pub(crate) is_rubber_band: bool,
@@ -81,11 +302,29 @@ pub(crate) struct CanvasInner {
     pub(crate) rubber_band_start_y: f64,
     pub(crate) rubber_band_end_x: f64,
     pub(crate) rubber_band_end_y: f64,
+    pub(crate) rubber_band_mode: RubberBandMode,
+
+    // 올가미 선택 상태 (점은 스크린 좌표)
+    pub(crate) lasso_select: bool,
+    pub(crate) lasso_mode: RegionMode,
+    pub(crate) is_lasso: bool,
+    pub(crate) lasso_points: Vec<Point>,
 
     // Undo/Redo 스택
     undo_stack: Vec<Action>,
     redo_stack: Vec<Action>,
 
+    // 이벤트 소싱 로그 및 타임랩스 재생 상태
+    pub(crate) op_log: Vec<LogEntry>,
+    pub(crate) replay: Option<Replay>,
+
+    // 실시간 협업 CRDT 문서 (협업 모드에서만 Some)
+    pub(crate) collab: Option<Document>,
+
+    // 원격 참여자 프레즌스
+    pub(crate) peers: Vec<Peer>,
+    pub(crate) peer_timeout_ms: f64,
+
     // 캐시된 dash 패턴 (매 프레임 재할당 방지)
     pub(crate) dash_cursor: JsArray,
     pub(crate) dash_selection: JsArray,
@@ -97,12 +336,27 @@ pub(crate) struct CanvasInner {
     pub(crate) logical_width: f64,
     pub(crate) logical_height: f64,
 
+    // 카메라 설정 (줌 범위, 휠 감도, 줌 단계)
+    pub(crate) camera_config: CameraConfig,
+
+    // 카메라 전환 애니메이션
+    pub(crate) camera_animation: Option<CameraAnimation>,
+    pub(crate) camera_animated: bool,
+    pub(crate) camera_duration_ms: f64,
+
+    // 이름 붙은 뷰 북마크 (연산 로그에 기록됨)
+    pub(crate) bookmarks: Vec<Bookmark>,
+    pub(crate) bookmark_index: Option<usize>,
+
     // 도형 그리기 상태
     pub(crate) is_drawing_shape: bool,
     pub(crate) shape_start_x: f64,
     pub(crate) shape_start_y: f64,
     pub(crate) shape_end_x: f64,
     pub(crate) shape_end_y: f64,
+    /// 그리는 중 눌린 수정자 키 (Shift: 각도/정사각형, Alt: 기준점 전환)
+    pub(crate) shape_modifiers: u32,
+    pub(crate) angle_step_deg: f64,
 
     // 팬 인터랙션
     pub(crate) is_panning: bool,
@@ -111,10 +365,42 @@ pub(crate) struct CanvasInner {
     pub(crate) pan_origin_x: f64,
     pub(crate) pan_origin_y: f64,
 
+    // 포인터 입력 상태 머신
+    pub(crate) interaction: Interaction,
+    /// 진행 중인 인터랙션을 시작한 포인터 ID
+    pub(crate) active_pointer: i32,
+    pub(crate) space_pan: bool,
+    pub(crate) cursor_style: &'static str,
+    pub(crate) gesture: Gesture,
+
+    // 키보드 단축키
+    pub(crate) keymap: Keymap,
+
+    // 레이어 (아래 → 위 순)와 새 요소가 들어갈 레이어
+    pub(crate) layers: Vec<Layer>,
+    pub(crate) active_layer: u32,
+    /// 다음 레이어 ID (삭제 후 undo로 되살릴 수 있으므로 ID를 재사용하지 않음)
+    pub(crate) next_layer_id: u32,
+
+    // 배경 그리드 / 스냅
+    pub(crate) grid: GridConfig,
+
+    // 오브젝트 스냅 / 스마트 가이드
+    pub(crate) object_snap: bool,
+    pub(crate) snap_guides: Vec<Guide>,
+
     // 렌더링 최적화: dirty flag
     pub(crate) needs_render: bool,
 }
 
+/// setLineDash용 [선, 간격] 배열
+fn dash_array(on: f64, off: f64) -> JsArray {
+    let arr = JsArray::new();
+    arr.push(&JsValue::from_f64(on));
+    arr.push(&JsValue::from_f64(off));
+    arr
+}
+
 impl CanvasInner {
     pub(crate) fn new(canvas_id: &str, dpr: f64) -> Result<CanvasInner, JsValue> {
         let document = web_sys::window()
@@ -140,7 +426,36 @@ impl CanvasInner {
         ctx.set_line_cap("round");
         ctx.set_line_join("round");
 
-        Ok(CanvasInner {
+        Ok(CanvasInner::with_context(
+            ctx,
+            dash_array(4.0, 4.0),
+            dash_array(6.0, 4.0),
+            logical_width,
+            logical_height,
+        ))
+    }
+
+    /// 렌더링 없이 상태만 다루는 테스트용 캔버스 (800x600, 기본 뷰)
+    #[cfg(test)]
+    pub(crate) fn for_test() -> CanvasInner {
+        CanvasInner::with_context(
+            JsValue::NULL.unchecked_into(),
+            JsValue::NULL.unchecked_into(),
+            JsValue::NULL.unchecked_into(),
+            800.0,
+            600.0,
+        )
+    }
+
+    /// 준비된 2D 컨텍스트/점선 패턴과 논리 크기로 초기 상태 생성
+    fn with_context(
+        ctx: CanvasRenderingContext2d,
+        dash_cursor: JsArray,
+        dash_selection: JsArray,
+        logical_width: f64,
+        logical_height: f64,
+    ) -> CanvasInner {
+        CanvasInner {
             ctx,
             elements: Vec::new(),
             current_element: None,
@@ -152,50 +467,76 @@ impl CanvasInner {
             cursor_x: 0.0,
             cursor_y: 0.0,
             show_cursor: false,
+            cursor_inside: false,
             tool_mode: ToolMode::Pen,
             selected_ids: HashSet::new(),
+            selection_anchor: None,
+            entered_groups: Vec::new(),
             clipboard: Vec::new(),
+            last_duplicate: None,
+            last_nudge: None,
             is_moving: false,
             move_start_x: 0.0,
             move_start_y: 0.0,
             move_total_dx: 0.0,
             move_total_dy: 0.0,
+            move_origin: None,
             is_rubber_band: false,
             rubber_band_start_x: 0.0,
             rubber_band_start_y: 0.0,
             rubber_band_end_x: 0.0,
             rubber_band_end_y: 0.0,
+            rubber_band_mode: RubberBandMode::Intersect,
+            lasso_select: false,
+            lasso_mode: RegionMode::Contain,
+            is_lasso: false,
+            lasso_points: Vec::new(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
-            dash_cursor: {
-                let arr = JsArray::new();
-                arr.push(&JsValue::from_f64(4.0));
-                arr.push(&JsValue::from_f64(4.0));
-                arr
-            },
-            dash_selection: {
-                let arr = JsArray::new();
-                arr.push(&JsValue::from_f64(6.0));
-                arr.push(&JsValue::from_f64(4.0));
-                arr
-            },
+            op_log: Vec::new(),
+            replay: None,
+            collab: None,
+            peers: Vec::new(),
+            peer_timeout_ms: DEFAULT_PEER_TIMEOUT_MS,
+            dash_cursor,
+            dash_selection,
             is_drawing_shape: false,
             shape_start_x: 0.0,
             shape_start_y: 0.0,
             shape_end_x: 0.0,
             shape_end_y: 0.0,
+            shape_modifiers: 0,
+            angle_step_deg: DEFAULT_ANGLE_STEP_DEG,
             zoom: 1.0,
             pan_x: 0.0,
             pan_y: 0.0,
             logical_width,
             logical_height,
+            camera_config: CameraConfig::default(),
+            camera_animation: None,
+            camera_animated: true,
+            camera_duration_ms: DEFAULT_CAMERA_DURATION_MS,
+            bookmarks: Vec::new(),
+            bookmark_index: None,
             is_panning: false,
             pan_start_x: 0.0,
             pan_start_y: 0.0,
             pan_origin_x: 0.0,
             pan_origin_y: 0.0,
+            interaction: Interaction::Idle,
+            active_pointer: 0,
+            space_pan: false,
+            cursor_style: "none",
+            gesture: Gesture::default(),
+            keymap: Keymap::default_keymap(),
+            layers: default_layers(),
+            active_layer: DEFAULT_LAYER_ID,
+            next_layer_id: DEFAULT_LAYER_ID + 1,
+            grid: GridConfig::default(),
+            object_snap: true,
+            snap_guides: Vec::new(),
             needs_render: false,
-        })
+        }
     }
 
     // ===== 기본 도구 =====
@@ -223,23 +564,30 @@ impl CanvasInner {
 
     /// 커서 위치 업데이트
     pub(crate) fn update_cursor(&mut self, x: f64, y: f64) {
-        self.cursor_x = x;
-        self.cursor_y = y;
+        self.track_cursor(x, y);
         self.show_cursor = true;
         self.needs_render = true;
     }
 
+    /// 커서 위치만 기록 (브러시 미리보기 없이, 붙여넣기 위치용)
+    pub(crate) fn track_cursor(&mut self, x: f64, y: f64) {
+        self.cursor_x = x;
+        self.cursor_y = y;
+        self.cursor_inside = true;
+    }
+
     /// 커서 숨기기
     pub(crate) fn hide_cursor(&mut self) {
         self.show_cursor = false;
+        self.cursor_inside = false;
         self.needs_render = true;
     }
 
     /// 통합 도구 전환
     pub(crate) fn set_tool_mode(&mut self, mode: &str) {
+        // 진행 중인 드래그는 확정 (이동은 undo에 기록, 그리던 선/도형은 추가)
+        self.finish_all_drags();
         self.is_eraser = false;
-        self.is_drawing = false;
-        self.is_drawing_shape = false;
         match mode {
             "pen" => self.tool_mode = ToolMode::Pen,
             "eraser" => {
@@ -252,9 +600,22 @@ impl CanvasInner {
             "circle" => self.tool_mode = ToolMode::Circle,
             _ => self.tool_mode = ToolMode::Pen,
         }
+        self.cursor_style = self.idle_cursor_style();
         self.needs_render = true;
     }
 
+    /// 현재 도구 이름 (set_tool_mode 인자와 동일)
+    pub(crate) fn get_tool_mode(&self) -> &'static str {
+        match self.tool_mode {
+            ToolMode::Pen => "pen",
+            ToolMode::Eraser => "eraser",
+            ToolMode::Select => "select",
+            ToolMode::Line => "line",
+            ToolMode::Rectangle => "rect",
+            ToolMode::Circle => "circle",
+        }
+    }
+
     /// 현재 도구가 도형 도구인지 확인
     pub(crate) fn is_shape_tool(&self) -> bool {
         matches!(
@@ -267,66 +628,43 @@ impl CanvasInner {
 
     /// 도형 그리기 시작 (월드 좌표)
     pub(crate) fn start_shape(&mut self, x: f64, y: f64) {
+        let (x, y) = self.snap_point(x, y);
         self.is_drawing_shape = true;
         self.shape_start_x = x;
         self.shape_start_y = y;
         self.shape_end_x = x;
         self.shape_end_y = y;
+        self.shape_modifiers = 0;
     }
 
-    /// 도형 프리뷰 업데이트 (월드 좌표)
-    pub(crate) fn update_shape(&mut self, x: f64, y: f64) {
+    /// 도형 프리뷰 업데이트 (월드 좌표, modifiers: Shift 각도/정사각형, Alt 기준점 전환)
+    pub(crate) fn update_shape(&mut self, x: f64, y: f64, modifiers: u32) {
         if !self.is_drawing_shape {
             return;
         }
+        let (x, y) = self.snap_point(x, y);
         self.shape_end_x = x;
         self.shape_end_y = y;
+        self.shape_modifiers = modifiers;
         self.needs_render = true;
     }
 
-    /// 도형 그리기 확정
-    pub(crate) fn finish_shape(&mut self) {
+    /// 도형 그리기 확정 (프리뷰와 같은 제약 적용)
+    pub(crate) fn finish_shape(&mut self, modifiers: u32) {
         if !self.is_drawing_shape {
             return;
         }
+        self.shape_modifiers = modifiers;
+        let shape = self.constrained_shape();
         self.is_drawing_shape = false;
+        self.clear_snap_guides();
+        self.needs_render = true;
 
-        let shape = match self.tool_mode {
-            ToolMode::Line => Shape::Line {
-                x1: self.shape_start_x,
-                y1: self.shape_start_y,
-                x2: self.shape_end_x,
-                y2: self.shape_end_y,
-            },
-            ToolMode::Rectangle => {
-                let x = self.shape_start_x.min(self.shape_end_x);
-                let y = self.shape_start_y.min(self.shape_end_y);
-                let w = (self.shape_end_x - self.shape_start_x).abs();
-                let h = (self.shape_end_y - self.shape_start_y).abs();
-                if w < 1.0 && h < 1.0 {
-                    self.needs_render = true;
-                    return;
-                }
-                Shape::Rect { x, y, w, h }
-            }
-            ToolMode::Circle => {
-                let dx = self.shape_end_x - self.shape_start_x;
-                let dy = self.shape_end_y - self.shape_start_y;
-                let r = (dx * dx + dy * dy).sqrt();
-                if r < 1.0 {
-                    self.needs_render = true;
-                    return;
-                }
-                Shape::Circle {
-                    cx: self.shape_start_x,
-                    cy: self.shape_start_y,
-                    r,
-                }
-            }
-            _ => {
-                self.needs_render = true;
-                return;
-            }
+        let shape = match shape {
+            Some(Shape::Rect { w, h, .. }) if w < 1.0 && h < 1.0 => return,
+            Some(Shape::Circle { r, .. }) if r < 1.0 => return,
+            Some(shape) => shape,
+            None => return,
         };
 
         let element = Element {
@@ -336,21 +674,24 @@ impl CanvasInner {
                 color: self.color.clone(),
                 width: self.line_width,
             },
+            layer: self.active_layer,
+            locked: false,
+            hidden: false,
         };
         self.next_id += 1;
 
-        self.undo_stack.push(Action::AddElement {
+        let action = self.scoped(Action::AddElement {
             element: element.clone(),
         });
-        self.redo_stack.clear();
-        self.elements.push(element);
-        self.needs_render = true;
+        self.push_action(action);
+        self.scope_mut().push(element);
     }
 
     // ===== 그리기 =====
 
     /// 그리기 시작 - 새 Freehand 요소 생성
     pub(crate) fn start_drawing(&mut self, x: f64, y: f64) {
+        let (x, y) = self.snap_to_grid(x, y);
         self.is_drawing = true;
         self.current_element = Some(Element {
             id: self.next_id,
@@ -365,6 +706,9 @@ impl CanvasInner {
                 },
                 width: self.line_width,
             },
+            layer: self.active_layer,
+            locked: false,
+            hidden: false,
         });
         self.next_id += 1;
     }
@@ -389,11 +733,11 @@ impl CanvasInner {
         if let Some(elem) = self.current_element.take() {
             if let Shape::Freehand { ref points } = elem.shape {
                 if points.len() > 1 {
-                    self.undo_stack.push(Action::AddElement {
+                    let action = self.scoped(Action::AddElement {
                         element: elem.clone(),
                     });
-                    self.redo_stack.clear();
-                    self.elements.push(elem);
+                    self.push_action(action);
+                    self.scope_mut().push(elem);
                 }
             }
         }
@@ -402,14 +746,14 @@ impl CanvasInner {
     /// 전체 지우기 (모든 요소 삭제)
     pub(crate) fn clear(&mut self) {
         if !self.elements.is_empty() {
-            self.undo_stack.push(Action::ClearAll {
+            self.push_action(Action::ClearAll {
                 elements: self.elements.clone(),
             });
-            self.redo_stack.clear();
         }
         self.elements.clear();
         self.current_element = None;
         self.selected_ids.clear();
+        self.entered_groups.clear();
         self.needs_render = true;
     }
 
@@ -433,12 +777,17 @@ impl CanvasInner {
 
     /// 좌표에서 요소 선택 시도 (역순 탐색으로 최상위 우선)
     pub(crate) fn try_select_at(&mut self, x: f64, y: f64, shift: bool) -> bool {
-        let mut hit_id: Option<u32> = None;
-        for elem in self.elements.iter().rev() {
-            if elem.hit_test(x, y) {
-                hit_id = Some(elem.id);
-                break;
-            }
+        let hit_at = |elements: Vec<&Element>| {
+            elements.into_iter().rev().find(|e| e.hit_test(x, y)).map(|e| e.id)
+        };
+        let mut hit_id = hit_at(self.pickable());
+        self.last_duplicate = None;
+
+        // 들어가 있는 그룹 밖을 클릭하면 그룹에서 나옴
+        if hit_id.is_none() && !self.entered_groups.is_empty() {
+            self.entered_groups.clear();
+            self.selected_ids.clear();
+            hit_id = hit_at(self.pickable());
         }
 
         self.needs_render = true;
@@ -448,10 +797,17 @@ impl CanvasInner {
                 if shift {
                     if !self.selected_ids.remove(&id) {
                         self.selected_ids.insert(id);
+                        if !self
+                            .selection_anchor
+                            .is_some_and(|a| self.selected_ids.contains(&a))
+                        {
+                            self.selection_anchor = Some(id);
+                        }
                     }
                 } else {
                     self.selected_ids.clear();
                     self.selected_ids.insert(id);
+                    self.selection_anchor = Some(id);
                 }
                 true
             }
@@ -466,16 +822,15 @@ impl CanvasInner {
 
     /// 전체 선택
     pub(crate) fn select_all(&mut self) {
-        self.selected_ids.clear();
-        for elem in &self.elements {
-            self.selected_ids.insert(elem.id);
-        }
+        self.selected_ids = self.pickable().iter().map(|e| e.id).collect();
+        self.last_duplicate = None;
         self.needs_render = true;
     }
 
     /// 선택 해제
     pub(crate) fn deselect_all(&mut self) {
         self.selected_ids.clear();
+        self.last_duplicate = None;
         self.needs_render = true;
     }
 
@@ -486,7 +841,7 @@ impl CanvasInner {
 
     /// 좌표가 선택된 요소 위에 있는지 확인
     pub(crate) fn is_over_selected(&self, x: f64, y: f64) -> bool {
-        for elem in &self.elements {
+        for elem in self.scope() {
             if self.selected_ids.contains(&elem.id) && elem.hit_test(x, y) {
                 return true;
             }
@@ -503,26 +858,38 @@ impl CanvasInner {
         self.move_start_y = y;
         self.move_total_dx = 0.0;
         self.move_total_dy = 0.0;
+        self.move_origin = union_bounds(
+            self.scope()
+                .iter()
+                .filter(|e| self.selected_ids.contains(&e.id))
+                .filter_map(|e| e.geometry_bounds()),
+        );
     }
 
-    /// 이동 중 - 선택된 요소들을 델타만큼 이동
+    /// 이동 중 - 시작점 기준 누적 이동량을 맞추도록 선택된 요소들을 이동
     pub(crate) fn move_selected(&mut self, x: f64, y: f64) {
         if !self.is_moving {
             return;
         }
-        let dx = x - self.move_start_x;
-        let dy = y - self.move_start_y;
+        let mut total_dx = x - self.move_start_x;
+        let mut total_dy = y - self.move_start_y;
 
-        for elem in &mut self.elements {
-            if self.selected_ids.contains(&elem.id) {
-                elem.translate(dx, dy);
-            }
+        // 스냅: 다른 요소 정렬/간격, 없으면 선택 영역 좌상단을 그리드에
+        if let Some(origin) = self.move_origin.clone() {
+            (total_dx, total_dy) = self.snap_move(&origin, total_dx, total_dy);
         }
 
-        self.move_total_dx += dx;
-        self.move_total_dy += dy;
-        self.move_start_x = x;
-        self.move_start_y = y;
+        let dx = total_dx - self.move_total_dx;
+        let dy = total_dy - self.move_total_dy;
+        self.needs_render = true;
+        if dx == 0.0 && dy == 0.0 {
+            return;
+        }
+
+        self.translate_selected(dx, dy);
+
+        self.move_total_dx = total_dx;
+        self.move_total_dy = total_dy;
         self.needs_render = true;
     }
 
@@ -531,69 +898,105 @@ impl CanvasInner {
         if self.is_moving
             && (self.move_total_dx != 0.0 || self.move_total_dy != 0.0)
         {
-            self.undo_stack.push(Action::MoveElements {
+            let action = self.scoped(Action::MoveElements {
                 ids: self.selected_ids.iter().cloned().collect(),
                 dx: self.move_total_dx,
                 dy: self.move_total_dy,
             });
-            self.redo_stack.clear();
+            self.push_action(action);
         }
         self.is_moving = false;
+        self.clear_snap_guides();
+    }
+
+    /// 현재 범위의 선택 요소를 (dx, dy)만큼 이동 (undo 기록 없음)
+    pub(crate) fn translate_selected(&mut self, dx: f64, dy: f64) {
+        let selected = std::mem::take(&mut self.selected_ids);
+        for elem in self.scope_mut().iter_mut() {
+            if selected.contains(&elem.id) {
+                elem.translate(dx, dy);
+            }
+        }
+        self.selected_ids = selected;
     }
 
     // ===== 클립보드 =====
 
     /// 선택된 요소 복사
     pub(crate) fn copy_selected(&mut self) {
-        self.clipboard.clear();
-        for elem in &self.elements {
-            if self.selected_ids.contains(&elem.id) {
-                self.clipboard.push(elem.clone());
-            }
-        }
+        self.clipboard = self
+            .scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .cloned()
+            .collect();
     }
 
-    /// 클립보드에서 붙여넣기 (오프셋 적용, 새 ID 부여)
+    /// 클립보드에서 붙여넣기 (커서 위치, 없으면 화면 중앙에 새 ID로)
     pub(crate) fn paste(&mut self) {
         if self.clipboard.is_empty() {
             return;
         }
+        let source = self.clipboard.clone();
+        self.paste_at_anchor(&source);
+    }
+
+    /// 선택된 요소 복제 (클립보드는 건드리지 않음).
+    /// 처음에는 제자리에 복제하고, 방금 만든 복제본을 옮긴 뒤 다시 복제하면
+    /// 원본→복제본 변위를 그대로 이어감 (step-and-repeat)
+    pub(crate) fn duplicate_selected(&mut self) {
+        let source: Vec<Element> = self
+            .scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .cloned()
+            .collect();
+        if source.is_empty() {
+            return;
+        }
+
+        let origin = combined_bounds(&source).map(|bb| (bb.min_x, bb.min_y));
+        let (dx, dy) = match (&self.last_duplicate, origin) {
+            (Some(step), Some((x, y))) if step.ids == self.selected_ids => {
+                (x - step.origin.0, y - step.origin.1)
+            }
+            _ => (0.0, 0.0),
+        };
+        self.insert_copies(&source, dx, dy);
+
+        self.last_duplicate = origin.map(|origin| DuplicateStep {
+            ids: self.selected_ids.clone(),
+            origin,
+        });
+    }
 
-        let offset = 20.0;
-        let start_id = self.next_id;
+    /// 원본들의 사본을 (dx, dy)만큼 옮겨 새 ID로 추가하고 선택 (하나의 undo 액션)
+    pub(crate) fn insert_copies(&mut self, source: &[Element], dx: f64, dy: f64) {
+        let mut next_id = self.next_id;
 
-        let pasted: Vec<Element> = self
-            .clipboard
+        // 그룹은 하위 요소까지 새 ID, 최상위에서는 활성 레이어로
+        let top_level = self.entered_groups.is_empty();
+        let pasted: Vec<Element> = source
             .iter()
-            .enumerate()
-            .map(|(i, orig)| {
+            .map(|orig| {
                 let mut e = orig.clone();
-                e.id = start_id + i as u32;
-                e.translate(offset, offset);
+                e.reassign_ids(&mut next_id);
+                e.translate(dx, dy);
+                if top_level {
+                    e.layer = self.active_layer;
+                }
                 e
             })
             .collect();
 
-        self.next_id = start_id + pasted.len() as u32;
-        self.selected_ids.clear();
-
-        for e in &pasted {
-            self.selected_ids.insert(e.id);
-            self.elements.push(e.clone());
-        }
+        self.next_id = next_id;
+        self.selected_ids = pasted.iter().map(|e| e.id).collect();
+        self.scope_mut().extend(pasted.iter().cloned());
 
-        self.undo_stack.push(Action::PasteElements {
+        let action = self.scoped(Action::PasteElements {
             elements: pasted,
         });
-        self.redo_stack.clear();
-
-        // 반복 붙여넣기 시 오프셋 누적을 위해 클립보드 갱신
-        self.clipboard = self
-            .elements
-            .iter()
-            .filter(|e| self.selected_ids.contains(&e.id))
-            .cloned()
-            .collect();
+        self.push_action(action);
 
         self.needs_render = true;
     }
@@ -601,7 +1004,7 @@ impl CanvasInner {
     /// 선택된 요소 삭제 (인덱스 저장으로 z-order 보존)
     pub(crate) fn delete_selected(&mut self) {
         let deleted: Vec<(usize, Element)> = self
-            .elements
+            .scope()
             .iter()
             .enumerate()
             .filter(|(_, e)| self.selected_ids.contains(&e.id))
@@ -609,14 +1012,13 @@ impl CanvasInner {
             .collect();
 
         if !deleted.is_empty() {
-            self.undo_stack.push(Action::DeleteElements {
+            let action = self.scoped(Action::DeleteElements {
                 elements: deleted,
             });
-            self.redo_stack.clear();
+            action.apply(&mut self.elements);
+            self.push_action(action);
         }
 
-        self.elements
-            .retain(|e| !self.selected_ids.contains(&e.id));
         self.selected_ids.clear();
         self.needs_render = true;
     }
@@ -633,6 +1035,29 @@ impl CanvasInner {
         !self.redo_stack.is_empty()
     }
 
+    /// 새 액션 기록 (undo 스택 + 연산 로그, redo 스택 무효화)
+    fn push_action(&mut self, action: Action) {
+        self.log_op(LogOp::Do {
+            action: action.clone(),
+        });
+        self.undo_stack.push(action);
+        self.redo_stack.clear();
+        self.last_nudge = None;
+    }
+
+    /// 직전 undo 항목을 교체 (요소에는 이미 반영된 상태, 연속 키보드 이동 병합용)
+    fn amend_action(&mut self, action: Action) {
+        if self.undo_stack.pop().is_none() {
+            self.push_action(action);
+            return;
+        }
+        self.log_op(LogOp::Amend {
+            action: action.clone(),
+        });
+        self.undo_stack.push(action);
+        self.redo_stack.clear();
+    }
+
     /// 실행 취소
     pub(crate) fn undo(&mut self) {
         let action = match self.undo_stack.pop() {
@@ -640,34 +1065,15 @@ impl CanvasInner {
             None => return,
         };
 
-        match &action {
-            Action::AddElement { element } => {
-                self.elements.retain(|e| e.id != element.id);
-            }
-            Action::DeleteElements { elements } => {
-                for (idx, e) in elements {
-                    let insert_at = (*idx).min(self.elements.len());
-                    self.elements.insert(insert_at, e.clone());
-                }
-            }
-            Action::MoveElements { ids, dx, dy } => {
-                for elem in &mut self.elements {
-                    if ids.contains(&elem.id) {
-                        elem.translate(-dx, -dy);
-                    }
-                }
-            }
-            Action::PasteElements { elements } => {
-                let ids: HashSet<u32> = elements.iter().map(|e| e.id).collect();
-                self.elements.retain(|e| !ids.contains(&e.id));
-            }
-            Action::ClearAll { elements } => {
-                self.elements = elements.clone();
-            }
-        }
+        action.revert(&mut self.elements);
+        self.log_op(LogOp::Undo);
+        self.last_nudge = None;
+        self.last_duplicate = None;
+        self.sync_layer_action(&action, false);
 
         self.redo_stack.push(action);
         self.selected_ids.clear();
+        self.validate_scope();
         self.needs_render = true;
     }
 
@@ -678,33 +1084,15 @@ impl CanvasInner {
             None => return,
         };
 
-        match &action {
-            Action::AddElement { element } => {
-                self.elements.push(element.clone());
-            }
-            Action::DeleteElements { elements } => {
-                let ids: HashSet<u32> = elements.iter().map(|(_, e)| e.id).collect();
-                self.elements.retain(|e| !ids.contains(&e.id));
-            }
-            Action::MoveElements { ids, dx, dy } => {
-                for elem in &mut self.elements {
-                    if ids.contains(&elem.id) {
-                        elem.translate(*dx, *dy);
-                    }
-                }
-            }
-            Action::PasteElements { elements } => {
-                for e in elements {
-                    self.elements.push(e.clone());
-                }
-            }
-            Action::ClearAll { .. } => {
-                self.elements.clear();
-            }
-        }
+        action.apply(&mut self.elements);
+        self.log_op(LogOp::Redo);
+        self.last_nudge = None;
+        self.last_duplicate = None;
+        self.sync_layer_action(&action, true);
 
         self.undo_stack.push(action);
         self.selected_ids.clear();
+        self.validate_scope();
         self.needs_render = true;
     }
 
@@ -729,7 +1117,20 @@ impl CanvasInner {
         self.needs_render = true;
     }
 
-    /// 러버밴드 선택 확정 — 스크린→월드 변환 후 교차 검사
+    /// 현재 드래그에 적용되는 러버밴드 판정 방식
+    pub(crate) fn rubber_band_region_mode(&self) -> RegionMode {
+        let left_to_right = self.rubber_band_end_x >= self.rubber_band_start_x;
+        self.rubber_band_mode.resolve(left_to_right)
+    }
+
+    /// 러버밴드 판정 방식 (mode: "contain" | "intersect" | "direction")
+    pub(crate) fn set_rubber_band_mode(&mut self, mode: &str) -> Result<(), String> {
+        self.rubber_band_mode = RubberBandMode::parse(mode)
+            .ok_or_else(|| format!("unknown rubber band mode: {}", mode))?;
+        Ok(())
+    }
+
+    /// 러버밴드 선택 확정 — 스크린→월드 변환 후 실제 도형 기하로 판정
     pub(crate) fn finish_rubber_band(&mut self, shift: bool) {
         if !self.is_rubber_band {
             return;
@@ -751,14 +1152,17 @@ impl CanvasInner {
         if !shift {
             self.selected_ids.clear();
         }
-
-        for elem in &self.elements {
-            if let Some(bb) = elem.bounding_box() {
-                if rect.intersects(&bb) {
-                    self.selected_ids.insert(elem.id);
-                }
-            }
-        }
+        self.last_duplicate = None;
+
+        let region = Region::rect(&rect);
+        let mode = self.rubber_band_region_mode();
+        let hits: Vec<u32> = self
+            .pickable()
+            .into_iter()
+            .filter(|e| region.selects(e, mode))
+            .map(|e| e.id)
+            .collect();
+        self.selected_ids.extend(hits);
 
         self.needs_render = true;
     }
@@ -777,10 +1181,12 @@ impl CanvasInner {
         (sy - self.pan_y) / self.zoom
     }
 
-    /// 커서 위치 기준 줌 (휠 이벤트용)
+    /// 커서 위치 기준 줌 (휠 이벤트용, 항상 즉시)
     pub(crate) fn zoom_at(&mut self, screen_x: f64, screen_y: f64, delta: f64) {
-        let factor = if delta < 0.0 { 1.1 } else { 1.0 / 1.1 };
-        let new_zoom = (self.zoom * factor).clamp(0.1, 10.0);
+        self.stop_camera_animation();
+
+        let factor = self.camera_config.wheel_zoom_factor(delta);
+        let new_zoom = self.camera_config.clamp_zoom(self.zoom * factor);
 
         let wx = (screen_x - self.pan_x) / self.zoom;
         let wy = (screen_y - self.pan_y) / self.zoom;
@@ -792,24 +1198,22 @@ impl CanvasInner {
         self.needs_render = true;
     }
 
-    /// 줌 레벨 직접 설정 (캔버스 중심 기준)
+    /// 줌 레벨 직접 설정 (캔버스 중심 기준, 진행 중인 전환의 목표 카메라 기준)
     pub(crate) fn set_zoom(&mut self, new_zoom: f64) {
-        let new_zoom = new_zoom.clamp(0.1, 10.0);
+        let new_zoom = self.camera_config.clamp_zoom(new_zoom);
+        let (zoom, pan_x, pan_y) = self.camera_target();
         let cx = self.logical_width / 2.0;
         let cy = self.logical_height / 2.0;
 
-        let wx = (cx - self.pan_x) / self.zoom;
-        let wy = (cy - self.pan_y) / self.zoom;
-
-        self.zoom = new_zoom;
-        self.pan_x = cx - wx * self.zoom;
-        self.pan_y = cy - wy * self.zoom;
+        let wx = (cx - pan_x) / zoom;
+        let wy = (cy - pan_y) / zoom;
 
-        self.needs_render = true;
+        self.set_camera(new_zoom, cx - wx * new_zoom, cy - wy * new_zoom);
     }
 
     /// 팬 시작 (스크린 좌표)
     pub(crate) fn start_pan(&mut self, sx: f64, sy: f64) {
+        self.stop_camera_animation();
         self.is_panning = true;
         self.pan_start_x = sx;
         self.pan_start_y = sy;
@@ -834,54 +1238,22 @@ impl CanvasInner {
 
     /// 전체 컨텐츠에 맞춤 (fit to view)
     pub(crate) fn fit_to_view(&mut self) {
-        if self.elements.is_empty() {
-            self.reset_view();
-            return;
-        }
-
-        let mut min_x = f64::INFINITY;
-        let mut min_y = f64::INFINITY;
-        let mut max_x = f64::NEG_INFINITY;
-        let mut max_y = f64::NEG_INFINITY;
-
-        for elem in &self.elements {
-            if let Some(bb) = elem.bounding_box() {
-                min_x = min_x.min(bb.min_x);
-                min_y = min_y.min(bb.min_y);
-                max_x = max_x.max(bb.max_x);
-                max_y = max_y.max(bb.max_y);
+        let bb = match combined_bounds(&self.elements) {
+            Some(bb) if bb.min_x < bb.max_x && bb.min_y < bb.max_y => bb,
+            _ => {
+                self.reset_view();
+                return;
             }
-        }
-
-        if min_x >= max_x || min_y >= max_y {
-            self.reset_view();
-            return;
-        }
-
-        let content_w = max_x - min_x;
-        let content_h = max_y - min_y;
-        let padding = 40.0;
-
-        let scale_x = (self.logical_width - padding * 2.0) / content_w;
-        let scale_y = (self.logical_height - padding * 2.0) / content_h;
-        let new_zoom = scale_x.min(scale_y).clamp(0.1, 10.0);
-
-        let center_x = (min_x + max_x) / 2.0;
-        let center_y = (min_y + max_y) / 2.0;
-
-        self.zoom = new_zoom;
-        self.pan_x = self.logical_width / 2.0 - center_x * self.zoom;
-        self.pan_y = self.logical_height / 2.0 - center_y * self.zoom;
+        };
 
-        self.needs_render = true;
+        let (zoom, pan_x, pan_y) = self.camera_for_bounds(&bb);
+        self.set_camera(zoom, pan_x, pan_y);
     }
 
     /// 뷰 초기화 (1:1)
     pub(crate) fn reset_view(&mut self) {
-        self.zoom = 1.0;
-        self.pan_x = 0.0;
-        self.pan_y = 0.0;
-        self.needs_render = true;
+        let zoom = self.camera_config.clamp_zoom(1.0);
+        self.set_camera(zoom, 0.0, 0.0);
     }
 }
 
@@ -891,6 +1263,9 @@ fn window() -> web_sys::Window {
     web_sys::window().expect("no global `window` exists")
 }
 
+/// 자기 자신을 다시 예약하는 rAF 콜백 슬롯
+type RafCallback = Rc<RefCell<Option<Closure<dyn FnMut()>>>>;
+
 fn request_animation_frame(f: &Closure<dyn FnMut()>) {
     window()
         .request_animation_frame(f.as_ref().unchecked_ref())
@@ -899,12 +1274,15 @@ fn request_animation_frame(f: &Closure<dyn FnMut()>) {
 
 /// Rust 소유 rAF 렌더 루프 시작
 fn start_render_loop(inner: Rc<RefCell<CanvasInner>>) {
-    let f: Rc<RefCell<Option<Closure<dyn FnMut()>>>> = Rc::new(RefCell::new(None));
+    let f: RafCallback = Rc::new(RefCell::new(None));
     let g = f.clone();
 
     *g.borrow_mut() = Some(Closure::wrap(Box::new(move || {
         match inner.try_borrow_mut() {
             Ok(mut state) => {
+                let now = js_sys::Date::now();
+                state.prune_stale_peers(now);
+                state.tick_camera(now);
                 if state.needs_render {
                     state.needs_render = false;
                     state.render();
@@ -931,6 +1309,22 @@ pub struct Canvas {
     loop_running: Cell<bool>,
 }
 
+impl Canvas {
+    /// 문서를 바꾸는 호출용 내부 상태. 타임랩스 재생 중에는 None (편집 무시)
+    fn edit(&self) -> Option<RefMut<'_, CanvasInner>> {
+        RefMut::filter_map(self.inner.borrow_mut(), |inner| {
+            inner.replay.is_none().then_some(inner)
+        })
+        .ok()
+    }
+
+    /// edit()의 Result 버전 (재생 중이면 에러)
+    fn edit_or_err(&self) -> Result<RefMut<'_, CanvasInner>, JsValue> {
+        self.edit()
+            .ok_or_else(|| JsValue::from_str("cannot edit the document during replay"))
+    }
+}
+
 #[wasm_bindgen]
 impl Canvas {
     #[wasm_bindgen(constructor)]
@@ -994,6 +1388,11 @@ impl Canvas {
         self.inner.borrow_mut().set_tool_mode(mode);
     }
 
+    #[wasm_bindgen]
+    pub fn get_tool_mode(&self) -> String {
+        self.inner.borrow().get_tool_mode().to_string()
+    }
+
     #[wasm_bindgen]
     pub fn is_shape_tool(&self) -> bool {
         self.inner.borrow().is_shape_tool()
@@ -1004,47 +1403,134 @@ impl Canvas {
         self.inner.borrow().is_drawing_shape
     }
 
-    // ===== 도형 도구 위임 =====
+    // ===== 포인터 입력 위임 (스크린 좌표) =====
 
     #[wasm_bindgen]
-    pub fn start_shape(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().start_shape(x, y);
+    pub fn pointer_down(
+        &self,
+        x: f64,
+        y: f64,
+        buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        self.inner
+            .borrow_mut()
+            .pointer_down(x, y, buttons, modifiers, pointer_type, pointer_id);
     }
 
     #[wasm_bindgen]
-    pub fn update_shape(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().update_shape(x, y);
+    pub fn pointer_move(
+        &self,
+        x: f64,
+        y: f64,
+        buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        self.inner
+            .borrow_mut()
+            .pointer_move(x, y, buttons, modifiers, pointer_type, pointer_id);
     }
 
     #[wasm_bindgen]
-    pub fn finish_shape(&self) {
-        self.inner.borrow_mut().finish_shape();
+    pub fn pointer_up(
+        &self,
+        x: f64,
+        y: f64,
+        buttons: u32,
+        modifiers: u32,
+        pointer_type: &str,
+        pointer_id: i32,
+    ) {
+        self.inner
+            .borrow_mut()
+            .pointer_up(x, y, buttons, modifiers, pointer_type, pointer_id);
     }
 
-    // ===== 그리기 위임 =====
-
     #[wasm_bindgen]
-    pub fn start_drawing(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().start_drawing(x, y);
+    pub fn pointer_leave(&self) {
+        self.inner.borrow_mut().pointer_leave();
     }
 
     #[wasm_bindgen]
-    pub fn draw(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().draw(x, y);
+    pub fn pointer_cancel(&self, pointer_id: i32) {
+        self.inner.borrow_mut().pointer_cancel(pointer_id);
     }
 
     #[wasm_bindgen]
-    pub fn stop_drawing(&self) {
-        self.inner.borrow_mut().stop_drawing();
+    pub fn set_space_pan(&self, active: bool) {
+        self.inner.borrow_mut().set_space_pan(active);
     }
 
     #[wasm_bindgen]
-    pub fn clear(&self) {
-        self.inner.borrow_mut().clear();
+    pub fn get_interaction(&self) -> String {
+        self.inner.borrow().interaction.as_str().to_string()
     }
 
     #[wasm_bindgen]
-    pub fn get_stroke_count(&self) -> usize {
+    pub fn get_cursor_style(&self) -> String {
+        self.inner.borrow().cursor_style.to_string()
+    }
+
+    // ===== 도형 도구 위임 =====
+
+    #[wasm_bindgen]
+    pub fn start_shape(&self, x: f64, y: f64) {
+        if let Some(mut inner) = self.edit() {
+            inner.start_shape(x, y);
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn update_shape(&self, x: f64, y: f64, modifiers: u32) {
+        self.inner.borrow_mut().update_shape(x, y, modifiers);
+    }
+
+    #[wasm_bindgen]
+    pub fn finish_shape(&self, modifiers: u32) {
+        self.inner.borrow_mut().finish_shape(modifiers);
+    }
+
+    /// Shift로 선을 그릴 때 각도 스냅 단위 (도, 기본 15)
+    #[wasm_bindgen]
+    pub fn set_angle_step(&self, degrees: f64) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_angle_step(degrees)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    // ===== 그리기 위임 =====
+
+    #[wasm_bindgen]
+    pub fn start_drawing(&self, x: f64, y: f64) {
+        if let Some(mut inner) = self.edit() {
+            inner.start_drawing(x, y);
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn draw(&self, x: f64, y: f64) {
+        self.inner.borrow_mut().draw(x, y);
+    }
+
+    #[wasm_bindgen]
+    pub fn stop_drawing(&self) {
+        self.inner.borrow_mut().stop_drawing();
+    }
+
+    #[wasm_bindgen]
+    pub fn clear(&self) {
+        if let Some(mut inner) = self.edit() {
+            inner.clear();
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn get_stroke_count(&self) -> usize {
         self.inner.borrow().elements.len()
     }
 
@@ -1062,12 +1548,14 @@ impl Canvas {
 
     #[wasm_bindgen]
     pub fn try_select_at(&self, x: f64, y: f64, shift: bool) -> bool {
-        self.inner.borrow_mut().try_select_at(x, y, shift)
+        self.edit().is_some_and(|mut inner| inner.try_select_at(x, y, shift))
     }
 
     #[wasm_bindgen]
     pub fn select_all(&self) {
-        self.inner.borrow_mut().select_all();
+        if let Some(mut inner) = self.edit() {
+            inner.select_all();
+        }
     }
 
     #[wasm_bindgen]
@@ -1075,6 +1563,22 @@ impl Canvas {
         self.inner.borrow_mut().deselect_all();
     }
 
+    /// 선택 요소와 같은 속성의 요소 모두 선택 (attr: "color" | "width" | "kind") → 선택 개수
+    #[wasm_bindgen]
+    pub fn select_similar(&self, attr: &str) -> Result<usize, JsValue> {
+        self.edit_or_err()?
+            .select_similar(attr)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// 쿼리 식에 맞는 요소 선택 (예: "kind == rect && width > 3") → 선택 개수
+    #[wasm_bindgen]
+    pub fn select_by_query(&self, query: &str) -> Result<usize, JsValue> {
+        self.edit_or_err()?
+            .select_by_query(query)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
     #[wasm_bindgen]
     pub fn has_selection(&self) -> bool {
         self.inner.borrow().has_selection()
@@ -1094,7 +1598,9 @@ impl Canvas {
 
     #[wasm_bindgen]
     pub fn start_move(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().start_move(x, y);
+        if let Some(mut inner) = self.edit() {
+            inner.start_move(x, y);
+        }
     }
 
     #[wasm_bindgen]
@@ -1114,14 +1620,301 @@ impl Canvas {
         self.inner.borrow_mut().copy_selected();
     }
 
+    /// 클립보드 내용을 커서 위치(캔버스 밖이면 화면 중앙)를 중심으로 붙여넣기
     #[wasm_bindgen]
     pub fn paste(&self) {
-        self.inner.borrow_mut().paste();
+        if let Some(mut inner) = self.edit() {
+            inner.paste();
+        }
+    }
+
+    /// 선택 요소 복제 (제자리, 복제본을 옮긴 뒤 반복하면 같은 변위로 계속)
+    #[wasm_bindgen]
+    pub fn duplicate_selected(&self) {
+        if let Some(mut inner) = self.edit() {
+            inner.duplicate_selected();
+        }
     }
 
     #[wasm_bindgen]
     pub fn delete_selected(&self) {
-        self.inner.borrow_mut().delete_selected();
+        if let Some(mut inner) = self.edit() {
+            inner.delete_selected();
+        }
+    }
+
+    /// 선택 요소를 (dx, dy)만큼 이동 (연속 호출은 undo 항목 하나로 병합)
+    #[wasm_bindgen]
+    pub fn nudge_selected(&self, dx: f64, dy: f64) -> bool {
+        self.edit().is_some_and(|mut inner| inner.nudge_selected(dx, dy))
+    }
+
+    /// 방향키 이동 (direction: "left" | "right" | "up" | "down").
+    /// 1, large(Shift)면 10, 그리드 스냅 중이면 그리드 간격 단위. 선택이 없으면 false
+    #[wasm_bindgen]
+    pub fn nudge(&self, direction: &str, large: bool) -> Result<bool, JsValue> {
+        let (dir_x, dir_y) = match direction {
+            "left" => (-1.0, 0.0),
+            "right" => (1.0, 0.0),
+            "up" => (0.0, -1.0),
+            "down" => (0.0, 1.0),
+            _ => {
+                return Err(JsValue::from_str(&format!(
+                    "unknown direction: {}",
+                    direction
+                )))
+            }
+        };
+        Ok(self
+            .edit()
+            .is_some_and(|mut inner| inner.nudge(dir_x, dir_y, large)))
+    }
+
+    /// 방향키 한 번의 이동량 (large: Shift)
+    #[wasm_bindgen]
+    pub fn get_nudge_step(&self, large: bool) -> f64 {
+        self.inner.borrow().nudge_step(large)
+    }
+
+    // ===== 시스템 클립보드 =====
+
+    /// 클립보드 JSON 페이로드의 MIME 타입
+    #[wasm_bindgen]
+    pub fn get_clipboard_mime(&self) -> String {
+        CLIPBOARD_MIME.to_string()
+    }
+
+    /// 선택 요소를 클립보드 JSON으로 (선택이 없으면 undefined)
+    #[wasm_bindgen]
+    pub fn copy_selection_json(&self) -> Option<String> {
+        self.inner.borrow().selection_to_json()
+    }
+
+    /// 선택 요소를 SVG로 (다른 앱 붙여넣기용 대체 포맷)
+    #[wasm_bindgen]
+    pub fn copy_selection_svg(&self) -> Option<String> {
+        self.inner.borrow().selection_to_svg()
+    }
+
+    /// 클립보드 JSON 또는 SVG 텍스트를 커서 위치(없으면 화면 중앙)에 붙여넣기 → 추가한 개수
+    #[wasm_bindgen]
+    pub fn paste_clipboard_data(&self, data: &str) -> Result<usize, JsValue> {
+        self.edit_or_err()?
+            .paste_clipboard_data(data)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    // ===== 정렬 / 분배 =====
+
+    /// edge: "left" | "center" | "right" | "top" | "middle" | "bottom",
+    /// relative_to: "selection" (선택 영역) | "first" (처음 선택한 요소)
+    #[wasm_bindgen]
+    pub fn align_selected(&self, edge: &str, relative_to: &str) -> Result<bool, JsValue> {
+        self.edit_or_err()?
+            .align_selected(edge, relative_to)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// axis: "horizontal" | "vertical" (3개 이상 선택 시)
+    #[wasm_bindgen]
+    pub fn distribute_selected(&self, axis: &str) -> Result<bool, JsValue> {
+        self.edit_or_err()?
+            .distribute_selected(axis)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    // ===== 뒤집기 =====
+
+    /// 선택 영역 중심 기준 뒤집기 (axis: "horizontal" | "vertical"). 선택이 없으면 false
+    #[wasm_bindgen]
+    pub fn flip_selected(&self, axis: &str) -> Result<bool, JsValue> {
+        self.edit_or_err()?
+            .flip_selected(axis)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    // ===== 그룹 =====
+
+    #[wasm_bindgen]
+    pub fn group_selected(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.group_selected())
+    }
+
+    #[wasm_bindgen]
+    pub fn ungroup_selected(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.ungroup_selected())
+    }
+
+    /// 더블클릭 (스크린 좌표) — 그룹 안으로 들어가거나 빈 곳이면 밖으로
+    #[wasm_bindgen]
+    pub fn pointer_double_click(&self, x: f64, y: f64) -> bool {
+        self.edit().is_some_and(|mut inner| inner.pointer_double_click(x, y))
+    }
+
+    #[wasm_bindgen]
+    pub fn exit_group(&self) -> bool {
+        self.inner.borrow_mut().exit_group()
+    }
+
+    /// 현재 들어가 있는 그룹 ID (최상위면 None)
+    #[wasm_bindgen]
+    pub fn get_entered_group(&self) -> Option<u32> {
+        self.inner.borrow().entered_groups.last().copied()
+    }
+
+    // ===== Z-order =====
+
+    /// mode: "front" | "back" | "forward" | "backward"
+    #[wasm_bindgen]
+    pub fn reorder_selected(&self, mode: &str) -> Result<bool, JsValue> {
+        self.edit_or_err()?
+            .reorder_selected(mode)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn bring_to_front(&self) -> bool {
+        self.edit()
+            .is_some_and(|mut inner| inner.reorder_selected("front") == Ok(true))
+    }
+
+    #[wasm_bindgen]
+    pub fn send_to_back(&self) -> bool {
+        self.edit()
+            .is_some_and(|mut inner| inner.reorder_selected("back") == Ok(true))
+    }
+
+    #[wasm_bindgen]
+    pub fn bring_forward(&self) -> bool {
+        self.edit()
+            .is_some_and(|mut inner| inner.reorder_selected("forward") == Ok(true))
+    }
+
+    #[wasm_bindgen]
+    pub fn send_backward(&self) -> bool {
+        self.edit()
+            .is_some_and(|mut inner| inner.reorder_selected("backward") == Ok(true))
+    }
+
+    // ===== 요소 잠금 / 숨김 =====
+
+    /// 선택된 요소 잠금 (그려지지만 선택/이동 불가, undo 가능)
+    #[wasm_bindgen]
+    pub fn lock_selected(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.lock_selected())
+    }
+
+    /// 현재 범위의 잠긴 요소 모두 잠금 해제
+    #[wasm_bindgen]
+    pub fn unlock_all(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.unlock_all())
+    }
+
+    /// 선택된 요소 숨기기 (그리기/SVG 내보내기 제외, undo 가능)
+    #[wasm_bindgen]
+    pub fn hide_selected(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.hide_selected())
+    }
+
+    /// 현재 범위의 숨긴 요소 모두 표시
+    #[wasm_bindgen]
+    pub fn show_all(&self) -> bool {
+        self.edit().is_some_and(|mut inner| inner.show_all())
+    }
+
+    #[wasm_bindgen]
+    pub fn set_element_locked(&self, id: u32, locked: bool) -> bool {
+        self.edit().is_some_and(|mut inner| inner.set_element_locked(id, locked))
+    }
+
+    #[wasm_bindgen]
+    pub fn set_element_hidden(&self, id: u32, hidden: bool) -> bool {
+        self.edit().is_some_and(|mut inner| inner.set_element_hidden(id, hidden))
+    }
+
+    // ===== 레이어 =====
+
+    /// 맨 위에 새 레이어 추가 후 활성화 → 새 레이어 ID (타임랩스 재생 중이면 undefined)
+    #[wasm_bindgen]
+    pub fn add_layer(&self, name: &str) -> Option<u32> {
+        self.edit().map(|mut inner| inner.add_layer(name))
+    }
+
+    /// 레이어와 그 안의 요소 삭제 (마지막 레이어는 삭제 불가)
+    #[wasm_bindgen]
+    pub fn remove_layer(&self, id: u32) -> bool {
+        self.edit().is_some_and(|mut inner| inner.remove_layer(id))
+    }
+
+    #[wasm_bindgen]
+    pub fn rename_layer(&self, id: u32, name: &str) -> bool {
+        self.edit().is_some_and(|mut inner| inner.rename_layer(id, name))
+    }
+
+    #[wasm_bindgen]
+    pub fn set_layer_visible(&self, id: u32, visible: bool) -> bool {
+        self.edit().is_some_and(|mut inner| inner.set_layer_visible(id, visible))
+    }
+
+    #[wasm_bindgen]
+    pub fn set_layer_locked(&self, id: u32, locked: bool) -> bool {
+        self.edit().is_some_and(|mut inner| inner.set_layer_locked(id, locked))
+    }
+
+    /// opacity: 0.0 ~ 1.0
+    #[wasm_bindgen]
+    pub fn set_layer_opacity(&self, id: u32, opacity: f64) -> bool {
+        self.edit().is_some_and(|mut inner| inner.set_layer_opacity(id, opacity))
+    }
+
+    /// 레이어 순서 변경 (index: 0이 맨 아래)
+    #[wasm_bindgen]
+    pub fn move_layer(&self, id: u32, index: usize) -> bool {
+        self.edit().is_some_and(|mut inner| inner.move_layer(id, index))
+    }
+
+    /// 새 요소가 그려질 레이어 지정
+    #[wasm_bindgen]
+    pub fn set_active_layer(&self, id: u32) -> bool {
+        self.inner.borrow_mut().set_active_layer(id)
+    }
+
+    /// 선택된 요소를 다른 레이어로 이동 (undo 가능)
+    #[wasm_bindgen]
+    pub fn move_selection_to_layer(&self, id: u32) -> bool {
+        self.edit().is_some_and(|mut inner| inner.move_selection_to_layer(id))
+    }
+
+    /// {"active": id, "layers": [{id, name, visible, locked, opacity}, ...]} (아래 → 위)
+    #[wasm_bindgen]
+    pub fn get_layers(&self) -> String {
+        self.inner.borrow().get_layers()
+    }
+
+    // ===== 키보드 단축키 =====
+
+    #[wasm_bindgen]
+    pub fn key_down(&self, key: &str, modifiers: u32) -> bool {
+        self.inner.borrow_mut().key_down(key, modifiers)
+    }
+
+    #[wasm_bindgen]
+    pub fn key_up(&self, key: &str, modifiers: u32) -> bool {
+        self.inner.borrow_mut().key_up(key, modifiers)
+    }
+
+    /// 키맵 설정 (JSON) — 충돌 목록을 JSON 배열로 반환
+    #[wasm_bindgen]
+    pub fn set_keymap(&self, json: &str) -> Result<String, JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_keymap(json)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_keymap(&self) -> String {
+        self.inner.borrow().get_keymap()
     }
 
     // ===== Undo / Redo 위임 =====
@@ -1138,19 +1931,25 @@ impl Canvas {
 
     #[wasm_bindgen]
     pub fn undo(&self) {
-        self.inner.borrow_mut().undo();
+        if let Some(mut inner) = self.edit() {
+            inner.undo();
+        }
     }
 
     #[wasm_bindgen]
     pub fn redo(&self) {
-        self.inner.borrow_mut().redo();
+        if let Some(mut inner) = self.edit() {
+            inner.redo();
+        }
     }
 
     // ===== 러버밴드 위임 =====
 
     #[wasm_bindgen]
     pub fn start_rubber_band(&self, x: f64, y: f64) {
-        self.inner.borrow_mut().start_rubber_band(x, y);
+        if let Some(mut inner) = self.edit() {
+            inner.start_rubber_band(x, y);
+        }
     }
 
     #[wasm_bindgen]
@@ -1168,6 +1967,70 @@ impl Canvas {
         self.inner.borrow().is_rubber_band
     }
 
+    /// mode: "contain" (완전히 포함) | "intersect" (선이 닿으면 선택) |
+    /// "direction" (왼쪽→오른쪽 드래그는 포함, 반대는 교차)
+    #[wasm_bindgen]
+    pub fn set_rubber_band_mode(&self, mode: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_rubber_band_mode(mode)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_rubber_band_mode(&self) -> String {
+        self.inner.borrow().rubber_band_mode.as_str().to_string()
+    }
+
+    // ===== 올가미 위임 =====
+
+    #[wasm_bindgen]
+    pub fn start_lasso(&self, x: f64, y: f64) {
+        if let Some(mut inner) = self.edit() {
+            inner.start_lasso(x, y);
+        }
+    }
+
+    #[wasm_bindgen]
+    pub fn update_lasso(&self, x: f64, y: f64) {
+        self.inner.borrow_mut().update_lasso(x, y);
+    }
+
+    #[wasm_bindgen]
+    pub fn finish_lasso(&self, shift: bool) {
+        self.inner.borrow_mut().finish_lasso(shift);
+    }
+
+    #[wasm_bindgen]
+    pub fn get_is_lasso(&self) -> bool {
+        self.inner.borrow().is_lasso
+    }
+
+    /// 선택 도구의 빈 곳 드래그를 올가미로 (false면 러버밴드)
+    #[wasm_bindgen]
+    pub fn set_lasso_select(&self, enabled: bool) {
+        self.inner.borrow_mut().set_lasso_select(enabled);
+    }
+
+    #[wasm_bindgen]
+    pub fn get_lasso_select(&self) -> bool {
+        self.inner.borrow().lasso_select
+    }
+
+    /// mode: "contain" (완전히 포함) | "intersect" (선이 닿으면 선택)
+    #[wasm_bindgen]
+    pub fn set_lasso_mode(&self, mode: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_lasso_mode(mode)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_lasso_mode(&self) -> String {
+        self.inner.borrow().lasso_mode.as_str().to_string()
+    }
+
     // ===== 줌 / 팬 위임 =====
 
     #[wasm_bindgen]
@@ -1195,6 +2058,12 @@ impl Canvas {
         self.inner.borrow().zoom
     }
 
+    /// 전환 애니메이션이 끝났을 때의 줌 (진행 중이 아니면 현재 줌). 줌 표시용
+    #[wasm_bindgen]
+    pub fn get_target_zoom(&self) -> f64 {
+        self.inner.borrow().camera_target().0
+    }
+
     #[wasm_bindgen]
     pub fn start_pan(&self, sx: f64, sy: f64) {
         self.inner.borrow_mut().start_pan(sx, sy);
@@ -1225,6 +2094,128 @@ impl Canvas {
         self.inner.borrow_mut().reset_view();
     }
 
+    /// 다음 줌 단계로 확대
+    #[wasm_bindgen]
+    pub fn zoom_in(&self) {
+        self.inner.borrow_mut().zoom_step(true);
+    }
+
+    /// 이전 줌 단계로 축소
+    #[wasm_bindgen]
+    pub fn zoom_out(&self) {
+        self.inner.borrow_mut().zoom_step(false);
+    }
+
+    /// 카메라 설정 (JSON: min_zoom, max_zoom, wheel_factor, trackpad_sensitivity,
+    /// wheel_mode "mouse" | "trackpad", zoom_steps, step_factor)
+    #[wasm_bindgen]
+    pub fn set_camera_config(&self, json: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_camera_config(json)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_camera_config(&self) -> String {
+        self.inner.borrow().get_camera_config()
+    }
+
+    // ===== 그리드 =====
+
+    /// 그리드 설정 (JSON: visible, style "lines" | "dots", spacing, major_every,
+    /// color, major_color, snap, export)
+    #[wasm_bindgen]
+    pub fn set_grid_config(&self, json: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_grid_config(json)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_grid_config(&self) -> String {
+        self.inner.borrow().get_grid_config()
+    }
+
+    #[wasm_bindgen]
+    pub fn set_grid_visible(&self, visible: bool) {
+        let mut inner = self.inner.borrow_mut();
+        inner.grid.visible = visible;
+        inner.needs_render = true;
+    }
+
+    #[wasm_bindgen]
+    pub fn set_snap_to_grid(&self, snap: bool) {
+        self.inner.borrow_mut().grid.snap = snap;
+    }
+
+    /// 다른 요소의 모서리/중심/간격에 스냅 (스마트 가이드 표시)
+    #[wasm_bindgen]
+    pub fn set_object_snap(&self, enabled: bool) {
+        self.inner.borrow_mut().set_object_snap(enabled);
+    }
+
+    #[wasm_bindgen]
+    pub fn get_object_snap(&self) -> bool {
+        self.inner.borrow().object_snap
+    }
+
+    #[wasm_bindgen]
+    pub fn zoom_to_selection(&self) -> bool {
+        self.inner.borrow_mut().zoom_to_selection()
+    }
+
+    #[wasm_bindgen]
+    pub fn zoom_to_element(&self, id: u32) -> bool {
+        self.inner.borrow_mut().zoom_to_element(id)
+    }
+
+    /// 카메라 전환 애니메이션 설정 (enabled=false면 즉시 이동)
+    #[wasm_bindgen]
+    pub fn set_camera_animation(&self, enabled: bool, duration_ms: f64) {
+        self.inner
+            .borrow_mut()
+            .set_camera_animation(enabled, duration_ms);
+    }
+
+    #[wasm_bindgen]
+    pub fn is_camera_animating(&self) -> bool {
+        self.inner.borrow().camera_animation.is_some()
+    }
+
+    // ===== 뷰 북마크 =====
+
+    #[wasm_bindgen]
+    pub fn save_bookmark(&self, name: &str) {
+        self.inner.borrow_mut().save_bookmark(name);
+    }
+
+    #[wasm_bindgen]
+    pub fn delete_bookmark(&self, name: &str) -> bool {
+        self.inner.borrow_mut().delete_bookmark(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn go_to_bookmark(&self, name: &str) -> bool {
+        self.inner.borrow_mut().go_to_bookmark(name)
+    }
+
+    #[wasm_bindgen]
+    pub fn next_bookmark(&self) -> Option<String> {
+        self.inner.borrow_mut().step_bookmark(true)
+    }
+
+    #[wasm_bindgen]
+    pub fn prev_bookmark(&self) -> Option<String> {
+        self.inner.borrow_mut().step_bookmark(false)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_bookmarks(&self) -> String {
+        self.inner.borrow().get_bookmarks()
+    }
+
     // ===== 렌더링 / 내보내기 =====
 
     #[wasm_bindgen]
@@ -1236,9 +2227,203 @@ impl Canvas {
     pub fn export_svg(&self) -> String {
         self.inner.borrow().export_svg()
     }
+
+    // ===== 연산 로그 / 타임랩스 =====
+
+    #[wasm_bindgen]
+    pub fn export_log(&self) -> String {
+        self.inner.borrow().export_log()
+    }
+
+    #[wasm_bindgen]
+    pub fn import_log(&self, json: &str) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .import_log(json)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_log_length(&self) -> usize {
+        self.inner.borrow().op_log.len()
+    }
+
+    #[wasm_bindgen]
+    pub fn start_replay(&self) {
+        self.inner.borrow_mut().start_replay();
+    }
+
+    #[wasm_bindgen]
+    pub fn stop_replay(&self) {
+        self.inner.borrow_mut().stop_replay();
+    }
+
+    #[wasm_bindgen]
+    pub fn replay_step(&self) -> bool {
+        self.inner.borrow_mut().replay_step()
+    }
+
+    #[wasm_bindgen]
+    pub fn replay_seek(&self, index: usize) {
+        self.inner.borrow_mut().replay_seek(index);
+    }
+
+    #[wasm_bindgen]
+    pub fn replay_advance_to(&self, elapsed_ms: f64) -> bool {
+        self.inner.borrow_mut().replay_advance_to(elapsed_ms)
+    }
+
+    #[wasm_bindgen]
+    pub fn get_replay_position(&self) -> Option<usize> {
+        self.inner.borrow().replay_position()
+    }
+
+    // ===== 실시간 협업 (CRDT) =====
+
+    #[wasm_bindgen]
+    pub fn enable_collaboration(&self, site_id: u32) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .enable_collaboration(site_id)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// 로컬 변경을 바이너리 업데이트 메시지로 (변경 없으면 빈 배열)
+    #[wasm_bindgen]
+    pub fn take_update(&self) -> Vec<u8> {
+        self.inner.borrow_mut().take_update()
+    }
+
+    #[wasm_bindgen]
+    pub fn encode_state(&self) -> Vec<u8> {
+        self.inner.borrow_mut().encode_state()
+    }
+
+    #[wasm_bindgen]
+    pub fn apply_update(&self, update: &[u8]) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .apply_update(update)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    // ===== 원격 프레즌스 =====
+
+    #[wasm_bindgen]
+    pub fn set_peer(
+        &self,
+        id: &str,
+        name: &str,
+        color: &str,
+        cursor_x: Option<f64>,
+        cursor_y: Option<f64>,
+        selected_ids: Vec<u32>,
+    ) {
+        self.inner
+            .borrow_mut()
+            .set_peer(id, name, color, cursor_x, cursor_y, selected_ids);
+    }
+
+    #[wasm_bindgen]
+    pub fn remove_peer(&self, id: &str) {
+        self.inner.borrow_mut().remove_peer(id);
+    }
+
+    /// 참여자 비활성 타임아웃 (ms). 0 이하나 유한하지 않은 값은 에러
+    #[wasm_bindgen]
+    pub fn set_peer_timeout(&self, timeout_ms: f64) -> Result<(), JsValue> {
+        self.inner
+            .borrow_mut()
+            .set_peer_timeout(timeout_ms)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    #[wasm_bindgen]
+    pub fn get_peer_count(&self) -> usize {
+        self.inner.borrow().peers.len()
+    }
 }
 
 #[wasm_bindgen(start)]
 pub fn main() {
     web_sys::console::log_1(&"Rust Canvas WASM (Retained Mode) loaded!".into());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect_origin(c: &CanvasInner, id: u32) -> (f64, f64) {
+        match c.elements.iter().find(|e| e.id == id).map(|e| &e.shape) {
+            Some(Shape::Rect { x, y, .. }) => (*x, *y),
+            _ => panic!("no rect {}", id),
+        }
+    }
+
+    /// (10, 10) 사각형 하나를 선택한 캔버스 (오브젝트 스냅 없음)
+    fn canvas() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.set_object_snap(false);
+        c.elements.push(Element {
+            id: 1,
+            shape: Shape::Rect {
+                x: 10.0,
+                y: 10.0,
+                w: 20.0,
+                h: 20.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        });
+        c.next_id = 2;
+        c.selected_ids.insert(1);
+        c
+    }
+
+    fn drag_selection(c: &mut CanvasInner, dx: f64, dy: f64) {
+        c.start_move(0.0, 0.0);
+        c.move_selected(dx, dy);
+        c.stop_move();
+    }
+
+    #[test]
+    fn duplicate_repeats_offset_of_moved_copy() {
+        let mut c = canvas();
+        c.duplicate_selected();
+        assert_eq!(rect_origin(&c, 2), (10.0, 10.0));
+        assert_eq!(c.selected_ids, [2].into());
+
+        drag_selection(&mut c, 30.0, 5.0);
+        c.duplicate_selected();
+        assert_eq!(rect_origin(&c, 3), (70.0, 20.0));
+        c.duplicate_selected();
+        assert_eq!(rect_origin(&c, 4), (100.0, 25.0));
+    }
+
+    #[test]
+    fn duplicate_step_is_forgotten_after_undo_or_reselection() {
+        let mut c = canvas();
+        c.duplicate_selected();
+        drag_selection(&mut c, 30.0, 5.0);
+        c.duplicate_selected();
+
+        c.undo();
+        c.redo();
+        c.selected_ids = [3].into();
+        c.duplicate_selected();
+        assert_eq!(rect_origin(&c, 4), (70.0, 20.0));
+
+        // 클릭으로 다시 선택해도 제자리 복제
+        drag_selection(&mut c, 30.0, 5.0);
+        c.deselect_all();
+        assert!(c.try_select_at(100.0, 30.0, false));
+        assert_eq!(c.selected_ids, [4].into());
+        c.duplicate_selected();
+        assert_eq!(rect_origin(&c, 5), (100.0, 25.0));
+    }
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// 2D 점 구조체
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -17,33 +17,72 @@ pub struct BoundingBox {
 }
 
 /// 스타일 (색상, 선 굵기)
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Style {
     pub color: String,
     pub width: f64,
 }
 
 /// 도형 종류
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum Shape {
     Freehand { points: Vec<Point> },
     Line { x1: f64, y1: f64, x2: f64, y2: f64 },
     Rect { x: f64, y: f64, w: f64, h: f64 },
     Circle { cx: f64, cy: f64, r: f64 },
+    /// 그룹 (자식 요소를 하나의 단위로 선택/이동, 중첩 가능)
+    Group { children: Vec<Element> },
+}
+
+impl Shape {
+    /// 도형 종류 이름 ("freehand" | "line" | "rect" | "circle" | "group")
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Shape::Freehand { .. } => "freehand",
+            Shape::Line { .. } => "line",
+            Shape::Rect { .. } => "rect",
+            Shape::Circle { .. } => "circle",
+            Shape::Group { .. } => "group",
+        }
+    }
 }
 
 /// 벡터 요소 (모든 그리기 객체의 공통 구조)
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct Element {
     pub id: u32,
     pub shape: Shape,
     pub style: Style,
+    /// 소속 레이어 ID (최상위 요소만 의미 있음)
+    #[serde(default)]
+    pub layer: u32,
+    /// 잠금 (그려지지만 선택/이동 불가)
+    #[serde(default)]
+    pub locked: bool,
+    /// 숨김 (그리지 않고 내보내지도 않음)
+    #[serde(default)]
+    pub hidden: bool,
 }
 
 impl Element {
     /// 바운딩 박스 계산 (선 굵기 반영)
     pub fn bounding_box(&self) -> Option<BoundingBox> {
-        let half_w = self.style.width / 2.0;
+        if let Shape::Group { children } = &self.shape {
+            return union_bounds(children.iter().filter_map(|c| c.bounding_box()));
+        }
+        self.bounds_inflated(self.style.width / 2.0)
+    }
+
+    /// 선 굵기를 제외한 기하 도형 자체의 바운딩 박스 (스냅 기준점 계산용)
+    pub fn geometry_bounds(&self) -> Option<BoundingBox> {
+        if let Shape::Group { children } = &self.shape {
+            return union_bounds(children.iter().filter_map(|c| c.geometry_bounds()));
+        }
+        self.bounds_inflated(0.0)
+    }
+
+    /// 기하 바운딩 박스를 half_w만큼 확장
+    fn bounds_inflated(&self, half_w: f64) -> Option<BoundingBox> {
         match &self.shape {
             Shape::Freehand { points } => {
                 if points.is_empty() {
@@ -71,12 +110,13 @@ impl Element {
                     max_y: y1.max(*y2) + half_w,
                 })
             }
+            // 음수 너비/높이(가져온 데이터)도 올바른 범위로
             Shape::Rect { x, y, w, h } => {
                 Some(BoundingBox {
-                    min_x: *x - half_w,
-                    min_y: *y - half_w,
-                    max_x: x + w + half_w,
-                    max_y: y + h + half_w,
+                    min_x: x.min(x + w) - half_w,
+                    min_y: y.min(y + h) - half_w,
+                    max_x: x.max(x + w) + half_w,
+                    max_y: y.max(y + h) + half_w,
                 })
             }
             Shape::Circle { cx, cy, r } => {
@@ -87,6 +127,8 @@ impl Element {
                     max_y: cy + r + half_w,
                 })
             }
+            // 그룹은 자식 기준 (bounding_box / geometry_bounds에서 처리)
+            Shape::Group { .. } => None,
         }
     }
 
@@ -113,6 +155,69 @@ impl Element {
                 *cx += dx;
                 *cy += dy;
             }
+            Shape::Group { children } => {
+                for c in children {
+                    c.translate(dx, dy);
+                }
+            }
+        }
+    }
+
+    /// 축을 기준으로 뒤집기 (horizontal: 세로선 x = center 기준 좌우 반전,
+    /// 아니면 가로선 y = center 기준 상하 반전). 두 번 적용하면 원래대로
+    pub fn flip(&mut self, horizontal: bool, center: f64) {
+        let mirror = |v: &mut f64| *v = 2.0 * center - *v;
+        match &mut self.shape {
+            Shape::Freehand { points } => {
+                for p in points {
+                    mirror(if horizontal { &mut p.x } else { &mut p.y });
+                }
+            }
+            Shape::Line { x1, y1, x2, y2 } => {
+                if horizontal {
+                    mirror(x1);
+                    mirror(x2);
+                } else {
+                    mirror(y1);
+                    mirror(y2);
+                }
+            }
+            // 원점은 반대쪽 모서리가 되도록 (너비/높이는 양수 유지)
+            Shape::Rect { x, y, w, h } => {
+                if horizontal {
+                    *x = 2.0 * center - (*x + *w);
+                } else {
+                    *y = 2.0 * center - (*y + *h);
+                }
+            }
+            Shape::Circle { cx, cy, .. } => mirror(if horizontal { cx } else { cy }),
+            Shape::Group { children } => {
+                for c in children {
+                    c.flip(horizontal, center);
+                }
+            }
+        }
+    }
+
+    /// 자신과 모든 하위 요소 중 가장 큰 ID
+    pub fn max_id(&self) -> u32 {
+        match &self.shape {
+            Shape::Group { children } => children
+                .iter()
+                .map(|c| c.max_id())
+                .fold(self.id, u32::max),
+            _ => self.id,
+        }
+    }
+
+    /// 자신과 모든 하위 요소에 새 ID 부여 (next_id부터 차례로)
+    pub fn reassign_ids(&mut self, next_id: &mut u32) {
+        self.id = *next_id;
+        *next_id += 1;
+        if let Shape::Group { children } = &mut self.shape {
+            for c in children {
+                c.reassign_ids(next_id);
+            }
         }
     }
 
@@ -154,6 +259,7 @@ impl Element {
                 let dist_from_center = ((px - cx).powi(2) + (py - cy).powi(2)).sqrt();
                 (dist_from_center - r).abs() <= threshold
             }
+            Shape::Group { children } => children.iter().any(|c| !c.hidden && c.hit_test(px, py)),
         }
     }
 }
@@ -189,4 +295,26 @@ impl BoundingBox {
             && self.min_y <= other.max_y
             && self.max_y >= other.min_y
     }
+
+    /// 두 바운딩 박스를 모두 포함하는 박스
+    pub fn union(&self, other: &BoundingBox) -> BoundingBox {
+        BoundingBox {
+            min_x: self.min_x.min(other.min_x),
+            min_y: self.min_y.min(other.min_y),
+            max_x: self.max_x.max(other.max_x),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+}
+
+/// 여러 바운딩 박스를 모두 포함하는 박스 (없으면 None)
+pub(crate) fn union_bounds(boxes: impl IntoIterator<Item = BoundingBox>) -> Option<BoundingBox> {
+    boxes.into_iter().reduce(|acc, bb| acc.union(&bb))
+}
+
+/// 여러 요소를 모두 포함하는 바운딩 박스 (요소가 없으면 None)
+pub(crate) fn combined_bounds<'a>(
+    elements: impl IntoIterator<Item = &'a Element>,
+) -> Option<BoundingBox> {
+    union_bounds(elements.into_iter().filter_map(|e| e.bounding_box()))
 }
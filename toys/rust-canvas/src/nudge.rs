@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+use crate::{Action, CanvasInner};
+
+// ===== 키보드 이동 (방향키) =====
+
+/// 기본 이동량 (그리드 스냅 중이면 그리드 간격)
+const NUDGE_UNIT: f64 = 1.0;
+
+/// Shift 이동량 배수
+const NUDGE_LARGE_FACTOR: f64 = 10.0;
+
+/// 연속 키보드 이동 기록 (같은 선택을 계속 이동하면 undo 항목 하나로 병합)
+pub(crate) struct NudgeRun {
+    /// 이동한 요소 ID
+    ids: HashSet<u32>,
+    /// 이동 당시 편집 범위 (그룹 안이면 그룹 ID)
+    group: Option<u32>,
+    /// 누적 이동량
+    dx: f64,
+    dy: f64,
+}
+
+impl CanvasInner {
+    /// 방향키 한 번의 이동량 (large: Shift)
+    pub(crate) fn nudge_step(&self, large: bool) -> f64 {
+        let unit = if self.grid.snap {
+            self.grid.spacing
+        } else {
+            NUDGE_UNIT
+        };
+        if large {
+            unit * NUDGE_LARGE_FACTOR
+        } else {
+            unit
+        }
+    }
+
+    /// 방향(dir_x, dir_y: -1/0/1)으로 한 칸 이동. 선택이 없으면 false
+    pub(crate) fn nudge(&mut self, dir_x: f64, dir_y: f64, large: bool) -> bool {
+        let step = self.nudge_step(large);
+        self.nudge_selected(dir_x * step, dir_y * step)
+    }
+
+    /// 선택된 요소를 (dx, dy)만큼 즉시 이동 (키보드 이동). 선택이 없으면 false.
+    /// 직전 undo 항목이 같은 선택의 키보드 이동이면 그 항목에 합침
+    pub(crate) fn nudge_selected(&mut self, dx: f64, dy: f64) -> bool {
+        if self.selected_ids.is_empty() {
+            return false;
+        }
+        self.translate_selected(dx, dy);
+
+        let group = self.entered_groups.last().copied();
+        let previous = self
+            .last_nudge
+            .take()
+            .filter(|run| run.ids == self.selected_ids && run.group == group);
+        let merge = previous.is_some();
+        let (dx, dy) = previous.map_or((dx, dy), |run| (run.dx + dx, run.dy + dy));
+
+        let ids = self.selected_ids.clone();
+        let action = self.scoped(Action::MoveElements {
+            ids: ids.iter().cloned().collect(),
+            dx,
+            dy,
+        });
+        if merge {
+            self.amend_action(action);
+        } else {
+            self.push_action(action);
+        }
+        self.last_nudge = Some(NudgeRun { ids, group, dx, dy });
+        self.needs_render = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oplog::LogOp;
+
+    /// 펜 선 1, 2를 그리고 1을 선택한 캔버스
+    fn canvas() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        for x in [0.0, 50.0] {
+            c.start_drawing(x, 0.0);
+            c.draw(x, 10.0);
+            c.stop_drawing();
+        }
+        c.selected_ids.insert(1);
+        c
+    }
+
+    fn left(c: &CanvasInner, id: u32) -> f64 {
+        let elem = c.elements.iter().find(|e| e.id == id).unwrap();
+        elem.geometry_bounds().unwrap().min_x
+    }
+
+    fn amends(c: &CanvasInner) -> usize {
+        c.op_log
+            .iter()
+            .filter(|entry| matches!(entry.op, LogOp::Amend { .. }))
+            .count()
+    }
+
+    #[test]
+    fn repeated_nudges_share_one_undo_entry() {
+        let mut c = canvas();
+        assert!(c.nudge(1.0, 0.0, false));
+        assert!(c.nudge(1.0, 0.0, true));
+        assert!(c.nudge(0.0, 1.0, false));
+        assert_eq!(left(&c, 1), 11.0);
+        assert_eq!(c.undo_stack.len(), 3);
+        assert_eq!(amends(&c), 2);
+
+        // 로그 재생도 같은 결과
+        let mut other = CanvasInner::for_test();
+        other.import_log(&c.export_log()).unwrap();
+        assert!(other.elements == c.elements);
+        assert_eq!(other.undo_stack.len(), 3);
+
+        c.undo();
+        assert_eq!(left(&c, 1), 0.0);
+        assert_eq!(c.undo_stack.len(), 2);
+        c.redo();
+        assert_eq!(left(&c, 1), 11.0);
+    }
+
+    #[test]
+    fn other_actions_and_selection_changes_break_the_run() {
+        let mut c = canvas();
+        c.nudge(1.0, 0.0, false);
+        c.reorder_selected("front").unwrap();
+        c.nudge(1.0, 0.0, false);
+        assert_eq!(c.undo_stack.len(), 5);
+
+        // 선택이 바뀌면 새 항목
+        c.selected_ids.insert(2);
+        c.nudge(1.0, 0.0, false);
+        assert_eq!(c.undo_stack.len(), 6);
+        c.nudge(1.0, 0.0, false);
+        assert_eq!(c.undo_stack.len(), 6);
+
+        // undo 뒤의 이동은 되돌린 항목에 합치지 않음
+        c.undo();
+        c.selected_ids = [1, 2].into();
+        c.nudge(1.0, 0.0, false);
+        assert_eq!(c.undo_stack.len(), 6);
+        c.undo();
+        assert_eq!((left(&c, 1), left(&c, 2)), (2.0, 50.0));
+        assert_eq!(amends(&c), 1);
+
+        // 선택이 없으면 이동도 기록도 없음
+        c.selected_ids.clear();
+        assert!(!c.nudge(1.0, 0.0, false));
+        assert_eq!(c.undo_stack.len(), 5);
+    }
+
+    #[test]
+    fn step_follows_grid_when_snapping() {
+        let mut c = canvas();
+        c.grid.snap = true;
+        assert_eq!(c.nudge_step(false), 20.0);
+        assert_eq!(c.nudge_step(true), 200.0);
+        c.grid.snap = false;
+        assert_eq!(c.nudge_step(true), 10.0);
+    }
+}
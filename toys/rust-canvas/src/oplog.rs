@@ -0,0 +1,424 @@
+use serde::{Deserialize, Serialize};
+
+use crate::camera::{remove_bookmark, upsert_bookmark, Bookmark};
+use crate::layers::{default_layers, Layer};
+use crate::models::Element;
+use crate::{Action, CanvasInner};
+
+// ===== 이벤트 소싱: 연산 로그 =====
+
+/// 로그에 기록되는 연산 (Undo/Redo는 스택 조작으로 재현)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "op")]
+pub(crate) enum LogOp {
+    Do {
+        action: Action,
+    },
+    Undo,
+    Redo,
+    /// 직전 undo 항목을 교체 (연속 키보드 이동 병합)
+    Amend {
+        action: Action,
+    },
+    /// 뷰 북마크 저장/갱신 (undo 대상 아님)
+    SetBookmark {
+        bookmark: Bookmark,
+    },
+    RemoveBookmark {
+        name: String,
+    },
+    /// 레이어 목록 전체 (undo 대상 아님)
+    SetLayers {
+        layers: Vec<Layer>,
+    },
+    /// 원격 업데이트 병합 결과 (협업). 병합 시 undo/redo 기록은 버려짐
+    Merge {
+        elements: Vec<Element>,
+        layers: Vec<Layer>,
+    },
+}
+
+/// 타임스탬프가 붙은 로그 항목 (t: epoch 기준 ms)
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct LogEntry {
+    pub(crate) t: f64,
+    #[serde(flatten)]
+    pub(crate) op: LogOp,
+}
+
+/// 로그를 처음부터 재생하는 문서 상태 (undo/redo 스택 포함)
+pub(crate) struct Replay {
+    pub(crate) elements: Vec<Element>,
+    pub(crate) undo_stack: Vec<Action>,
+    pub(crate) redo_stack: Vec<Action>,
+    pub(crate) bookmarks: Vec<Bookmark>,
+    pub(crate) layers: Vec<Layer>,
+    /// 다음에 적용할 로그 인덱스
+    pub(crate) cursor: usize,
+}
+
+impl Replay {
+    pub(crate) fn new() -> Replay {
+        Replay {
+            elements: Vec::new(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            bookmarks: Vec::new(),
+            layers: default_layers(),
+            cursor: 0,
+        }
+    }
+
+    /// 로그 항목 하나 적용
+    pub(crate) fn apply(&mut self, op: &LogOp) {
+        match op {
+            LogOp::Do { action } => {
+                action.apply(&mut self.elements);
+                self.undo_stack.push(action.clone());
+                self.redo_stack.clear();
+            }
+            LogOp::Undo => {
+                if let Some(action) = self.undo_stack.pop() {
+                    action.revert(&mut self.elements);
+                    self.redo_stack.push(action);
+                }
+            }
+            LogOp::Amend { action } => {
+                if let Some(previous) = self.undo_stack.pop() {
+                    previous.revert(&mut self.elements);
+                }
+                action.apply(&mut self.elements);
+                self.undo_stack.push(action.clone());
+                self.redo_stack.clear();
+            }
+            LogOp::Redo => {
+                if let Some(action) = self.redo_stack.pop() {
+                    action.apply(&mut self.elements);
+                    self.undo_stack.push(action);
+                }
+            }
+            LogOp::SetBookmark { bookmark } => {
+                upsert_bookmark(&mut self.bookmarks, bookmark.clone());
+            }
+            LogOp::RemoveBookmark { name } => {
+                remove_bookmark(&mut self.bookmarks, name);
+            }
+            LogOp::SetLayers { layers } => {
+                self.layers = layers.clone();
+            }
+            LogOp::Merge { elements, layers } => {
+                self.elements = elements.clone();
+                self.layers = layers.clone();
+                self.undo_stack.clear();
+                self.redo_stack.clear();
+            }
+        }
+        self.cursor += 1;
+    }
+}
+
+/// 현재 시각 (epoch ms). 브라우저 밖(네이티브 테스트)에서는 0
+pub(crate) fn now_ms() -> f64 {
+    if cfg!(target_arch = "wasm32") {
+        js_sys::Date::now()
+    } else {
+        0.0
+    }
+}
+
+impl CanvasInner {
+    /// 연산 로그에 항목 추가
+    pub(crate) fn log_op(&mut self, op: LogOp) {
+        self.op_log.push(LogEntry { t: now_ms(), op });
+    }
+
+    /// 연산 로그 JSON 직렬화
+    pub(crate) fn export_log(&self) -> String {
+        serde_json::to_string(&self.op_log).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// 로그로부터 문서 재구성 (undo/redo 스택까지 복원)
+    pub(crate) fn import_log(&mut self, json: &str) -> Result<(), String> {
+        let entries: Vec<LogEntry> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let mut replay = Replay::new();
+        for entry in &entries {
+            replay.apply(&entry.op);
+        }
+
+        self.elements = replay.elements;
+        self.undo_stack = replay.undo_stack;
+        self.redo_stack = replay.redo_stack;
+        self.last_nudge = None;
+        self.bookmarks = replay.bookmarks;
+        self.restore_layers(replay.layers);
+        self.bookmark_index = None;
+        self.op_log = entries;
+        self.current_element = None;
+        self.selected_ids.clear();
+        self.entered_groups.clear();
+        self.replay = None;
+        self.next_id = self.next_id.max(self.max_logged_id() + 1);
+        if let Some(max) = self.max_logged_layer_id() {
+            self.reserve_layer_id(max);
+        }
+        self.needs_render = true;
+        Ok(())
+    }
+
+    /// 로그에 등장한 가장 큰 요소 ID (ID 재사용 방지)
+    fn max_logged_id(&self) -> u32 {
+        self.op_log
+            .iter()
+            .filter_map(|entry| match &entry.op {
+                LogOp::Do { action } => action.max_id(),
+                LogOp::Merge { elements, .. } => elements.iter().map(|e| e.max_id()).max(),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// 로그에 등장한 가장 큰 레이어 ID (삭제된 레이어 포함)
+    fn max_logged_layer_id(&self) -> Option<u32> {
+        self.op_log
+            .iter()
+            .filter_map(|entry| match &entry.op {
+                LogOp::SetLayers { layers } | LogOp::Merge { layers, .. } => {
+                    layers.iter().map(|l| l.id).max()
+                }
+                _ => None,
+            })
+            .max()
+    }
+
+    // ===== 타임랩스 재생 =====
+
+    /// 재생 시작 (빈 문서에서 출발). 재생 중에는 편집이 막히므로 진행 중인 드래그는 확정
+    pub(crate) fn start_replay(&mut self) {
+        self.finish_all_drags();
+        self.replay = Some(Replay::new());
+        self.needs_render = true;
+    }
+
+    /// 재생 종료 (라이브 문서로 복귀)
+    pub(crate) fn stop_replay(&mut self) {
+        self.replay = None;
+        self.needs_render = true;
+    }
+
+    /// 다음 로그 항목 하나 적용. 더 적용할 항목이 없으면 false
+    pub(crate) fn replay_step(&mut self) -> bool {
+        let Some(replay) = self.replay.as_mut() else {
+            return false;
+        };
+        let Some(entry) = self.op_log.get(replay.cursor) else {
+            return false;
+        };
+        replay.apply(&entry.op);
+        self.needs_render = true;
+        true
+    }
+
+    /// 특정 로그 위치로 이동 (index개 항목이 적용된 상태)
+    pub(crate) fn replay_seek(&mut self, index: usize) {
+        let target = index.min(self.op_log.len());
+        let needs_restart = match &self.replay {
+            Some(replay) => replay.cursor > target,
+            None => true,
+        };
+        if needs_restart {
+            self.replay = Some(Replay::new());
+        }
+        if let Some(replay) = self.replay.as_mut() {
+            while replay.cursor < target {
+                replay.apply(&self.op_log[replay.cursor].op);
+            }
+        }
+        self.needs_render = true;
+    }
+
+    /// 첫 항목 기준 경과 시간(ms)까지의 항목을 모두 적용. 끝에 도달하면 false
+    pub(crate) fn replay_advance_to(&mut self, elapsed_ms: f64) -> bool {
+        let Some(start) = self.op_log.first().map(|e| e.t) else {
+            return false;
+        };
+        let Some(replay) = self.replay.as_mut() else {
+            return false;
+        };
+        let mut applied = false;
+        while let Some(entry) = self.op_log.get(replay.cursor) {
+            if entry.t - start > elapsed_ms {
+                break;
+            }
+            replay.apply(&entry.op);
+            applied = true;
+        }
+        if applied {
+            self.needs_render = true;
+        }
+        replay.cursor < self.op_log.len()
+    }
+
+    /// 재생 중인 로그 위치 (재생 중이 아니면 None)
+    pub(crate) fn replay_position(&self) -> Option<usize> {
+        self.replay.as_ref().map(|r| r.cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keymap::Command;
+
+    /// 펜으로 (x, 0)–(x, 10) 선 하나 그리기
+    fn stroke(c: &mut CanvasInner, x: f64) {
+        c.start_drawing(x, 0.0);
+        c.draw(x, 10.0);
+        c.stop_drawing();
+    }
+
+    fn ids(elements: &[Element]) -> Vec<u32> {
+        elements.iter().map(|e| e.id).collect()
+    }
+
+    /// 여러 종류의 연산이 섞인 로그를 가진 캔버스
+    fn edited() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        stroke(&mut c, 0.0);
+        stroke(&mut c, 10.0);
+        stroke(&mut c, 20.0);
+        c.selected_ids.insert(1);
+        c.nudge_selected(5.0, 0.0);
+        c.nudge_selected(5.0, 0.0);
+        c.add_layer("top");
+        c.selected_ids.insert(3);
+        c.delete_selected();
+        c.undo();
+        c.undo();
+        c.redo();
+        c
+    }
+
+    #[test]
+    fn import_round_trip_restores_document_and_history() {
+        let c = edited();
+        let mut other = CanvasInner::for_test();
+        other.import_log(&c.export_log()).unwrap();
+
+        assert!(other.elements == c.elements);
+        assert!(other.layers == c.layers);
+        assert_eq!(other.undo_stack.len(), c.undo_stack.len());
+        assert_eq!(other.redo_stack.len(), c.redo_stack.len());
+        assert_eq!(other.next_id, c.next_id);
+
+        // 복원된 undo 스택으로 라이브 문서와 같은 결과
+        let mut live = edited();
+        live.undo();
+        other.undo();
+        assert!(other.elements == live.elements);
+
+        assert!(other.import_log("not json").is_err());
+    }
+
+    #[test]
+    fn bookmarks_survive_export_and_import() {
+        let mut c = CanvasInner::for_test();
+        c.set_camera_animation(false, 0.0);
+        c.jump_camera(2.0, -400.0, -300.0);
+        c.save_bookmark("a");
+        c.jump_camera(0.5, 100.0, 50.0);
+        c.save_bookmark("b");
+        c.save_bookmark("c");
+        assert!(c.delete_bookmark("c"));
+        // 같은 이름은 교체 (순서 유지)
+        c.jump_camera(1.0, 10.0, 20.0);
+        c.save_bookmark("a");
+
+        // 화면 크기가 달라도 화면 중심의 월드 좌표가 유지됨
+        let mut other = CanvasInner::for_test();
+        other.set_camera_animation(false, 0.0);
+        other.logical_width = 400.0;
+        other.import_log(&c.export_log()).unwrap();
+        assert_eq!(other.get_bookmarks(), c.get_bookmarks());
+        assert!(!other.go_to_bookmark("c"));
+
+        assert!(other.go_to_bookmark("b"));
+        assert_eq!(other.zoom, 0.5);
+        // 저장 시 화면 중심 (400 - 100) / 0.5, (300 - 50) / 0.5
+        assert_eq!(other.screen_to_world_x(200.0), 600.0);
+        assert_eq!(other.screen_to_world_y(300.0), 500.0);
+
+        assert!(other.go_to_bookmark("a"));
+        assert_eq!((other.zoom, other.pan_x, other.pan_y), (1.0, -190.0, 20.0));
+        assert_eq!(other.step_bookmark(true).as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn replay_reaches_live_document() {
+        let mut c = edited();
+        c.start_replay();
+        assert_eq!(c.replay_position(), Some(0));
+        assert!(c.replay.as_ref().unwrap().elements.is_empty());
+
+        c.replay_seek(3);
+        assert_eq!(ids(&c.replay.as_ref().unwrap().elements), vec![1, 2, 3]);
+
+        while c.replay_step() {}
+        let replay = c.replay.as_ref().unwrap();
+        assert!(replay.elements == c.elements);
+        assert!(replay.layers == c.layers);
+
+        // 뒤로 이동하면 처음부터 다시 적용
+        c.replay_seek(1);
+        assert_eq!(ids(&c.replay.as_ref().unwrap().elements), vec![1]);
+        c.stop_replay();
+        assert_eq!(c.replay_position(), None);
+    }
+
+    #[test]
+    fn replay_blocks_edits() {
+        let mut c = edited();
+        let before = c.elements.clone();
+        let log_len = c.op_log.len();
+        c.set_tool_mode("pen");
+        c.start_replay();
+
+        c.pointer_down(100.0, 100.0, 1, 0, "mouse", 1);
+        assert!(c.interaction == crate::input::Interaction::Panning);
+        c.pointer_move(150.0, 100.0, 1, 0, "mouse", 1);
+        c.pointer_up(150.0, 100.0, 0, 0, "mouse", 1);
+
+        c.selected_ids.insert(2);
+        assert!(!c.execute_command(Command::Delete));
+        assert!(!c.execute_command(Command::Undo));
+        assert!(c.execute_command(Command::ZoomIn));
+
+        assert!(c.elements == before);
+        assert_eq!(c.op_log.len(), log_len);
+    }
+
+    #[test]
+    fn remote_merges_are_logged() {
+        let mut a = CanvasInner::for_test();
+        let mut b = CanvasInner::for_test();
+        a.enable_collaboration(1).unwrap();
+        b.enable_collaboration(2).unwrap();
+        stroke(&mut a, 0.0);
+        b.apply_update(&a.take_update()).unwrap();
+        stroke(&mut b, 10.0);
+        b.add_layer("remote");
+        a.apply_update(&b.take_update()).unwrap();
+        stroke(&mut a, 20.0);
+
+        let mut copy = CanvasInner::for_test();
+        copy.import_log(&a.export_log()).unwrap();
+        assert!(copy.elements == a.elements);
+        assert!(copy.layers == a.layers);
+        assert_eq!(copy.elements.len(), 3);
+
+        a.start_replay();
+        while a.replay_step() {}
+        assert!(a.replay.as_ref().unwrap().elements == a.elements);
+    }
+}
@@ -0,0 +1,235 @@
+use crate::group::find_element;
+use crate::layers::layer_index;
+use crate::models::{Element, Point};
+use crate::oplog::now_ms;
+use crate::CanvasInner;
+
+// ===== 원격 참여자 프레즌스 (커서 / 선택) =====
+
+/// 기본 비활성 타임아웃 (ms)
+pub(crate) const DEFAULT_PEER_TIMEOUT_MS: f64 = 30_000.0;
+
+/// 원격 참여자 상태
+pub(crate) struct Peer {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) color: String,
+    /// 커서 월드 좌표 (캔버스 밖이면 None)
+    pub(crate) cursor: Option<Point>,
+    pub(crate) selected_ids: Vec<u32>,
+    /// 마지막 갱신 시각 (epoch 기준 ms)
+    pub(crate) last_seen: f64,
+}
+
+impl CanvasInner {
+    /// 참여자 추가 또는 갱신
+    pub(crate) fn set_peer(
+        &mut self,
+        id: &str,
+        name: &str,
+        color: &str,
+        cursor_x: Option<f64>,
+        cursor_y: Option<f64>,
+        selected_ids: Vec<u32>,
+    ) {
+        let now = now_ms();
+        let cursor = match (cursor_x, cursor_y) {
+            (Some(x), Some(y)) => Some(Point { x, y }),
+            _ => None,
+        };
+
+        match self.peers.iter_mut().find(|p| p.id == id) {
+            Some(peer) => {
+                peer.name = name.to_string();
+                peer.color = color.to_string();
+                peer.cursor = cursor;
+                peer.selected_ids = selected_ids;
+                peer.last_seen = now;
+            }
+            None => self.peers.push(Peer {
+                id: id.to_string(),
+                name: name.to_string(),
+                color: color.to_string(),
+                cursor,
+                selected_ids,
+                last_seen: now,
+            }),
+        }
+        self.needs_render = true;
+    }
+
+    /// 참여자 제거
+    pub(crate) fn remove_peer(&mut self, id: &str) {
+        let before = self.peers.len();
+        self.peers.retain(|p| p.id != id);
+        if self.peers.len() != before {
+            self.needs_render = true;
+        }
+    }
+
+    /// 비활성 타임아웃 설정 (양의 유한한 ms만 허용)
+    pub(crate) fn set_peer_timeout(&mut self, timeout_ms: f64) -> Result<(), String> {
+        if !timeout_ms.is_finite() || timeout_ms <= 0.0 {
+            return Err("peer timeout must be a positive finite number".to_string());
+        }
+        self.peer_timeout_ms = timeout_ms;
+        Ok(())
+    }
+
+    /// 타임아웃이 지난 참여자 제거 (rAF 루프에서 매 프레임 호출)
+    pub(crate) fn prune_stale_peers(&mut self, now: f64) {
+        let timeout = self.peer_timeout_ms;
+        let before = self.peers.len();
+        self.peers.retain(|p| now - p.last_seen <= timeout);
+        if self.peers.len() != before {
+            self.needs_render = true;
+        }
+    }
+
+    /// 참여자 선택 영역 + 커서 그리기 (월드 스페이스, 크기는 줌과 무관하게 유지)
+    pub(crate) fn draw_peers(&self) {
+        if self.peers.is_empty() {
+            return;
+        }
+
+        let px = 1.0 / self.zoom;
+        let padding = 6.0;
+
+        self.ctx.save();
+
+        for peer in &self.peers {
+            // 선택 아웃라인
+            self.ctx.set_stroke_style_str(&peer.color);
+            self.ctx.set_line_width(1.5 * px);
+            for id in &peer.selected_ids {
+                let Some(elem) = self.visible_element(*id) else {
+                    continue;
+                };
+                if let Some(bb) = elem.bounding_box() {
+                    self.ctx.begin_path();
+                    self.ctx.rect(
+                        bb.min_x - padding,
+                        bb.min_y - padding,
+                        (bb.max_x - bb.min_x) + padding * 2.0,
+                        (bb.max_y - bb.min_y) + padding * 2.0,
+                    );
+                    self.ctx.stroke();
+                }
+            }
+
+            // 커서 화살표 + 이름 라벨
+            let Some(ref c) = peer.cursor else {
+                continue;
+            };
+            self.ctx.set_fill_style_str(&peer.color);
+            self.ctx.begin_path();
+            self.ctx.move_to(c.x, c.y);
+            self.ctx.line_to(c.x, c.y + 16.0 * px);
+            self.ctx.line_to(c.x + 4.5 * px, c.y + 12.0 * px);
+            self.ctx.line_to(c.x + 11.0 * px, c.y + 12.0 * px);
+            self.ctx.close_path();
+            self.ctx.fill();
+
+            if !peer.name.is_empty() {
+                self.ctx.set_font(&format!("{}px sans-serif", 12.0 * px));
+                let _ = self
+                    .ctx
+                    .fill_text(&peer.name, c.x + 12.0 * px, c.y + 26.0 * px);
+            }
+        }
+
+        self.ctx.restore();
+    }
+
+    /// 화면에 그려지는 요소 (자신이나 최상위 요소가 숨겨졌거나 숨긴 레이어에 있으면 None)
+    fn visible_element(&self, id: u32) -> Option<&Element> {
+        let (root, elem) = self.elements.iter().find_map(|root| {
+            find_element(std::slice::from_ref(root), id).map(|elem| (root, elem))
+        })?;
+        let layer_visible = self
+            .layers
+            .get(layer_index(&self.layers, root.layer))
+            .is_none_or(|l| l.visible);
+        (layer_visible && !root.hidden && !elem.hidden).then_some(elem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+    use crate::CanvasInner;
+
+    fn rect(id: u32) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect {
+                x: 0.0,
+                y: 0.0,
+                w: 10.0,
+                h: 10.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn peer(c: &mut CanvasInner, id: &str) {
+        c.set_peer(id, id, "#ff0000", Some(1.0), Some(2.0), vec![]);
+    }
+
+    #[test]
+    fn stale_peers_are_pruned_after_timeout() {
+        let mut c = CanvasInner::for_test();
+        // 네이티브 테스트에서 now_ms()는 0
+        peer(&mut c, "a");
+        c.prune_stale_peers(30_000.0);
+        assert_eq!(c.peers.len(), 1);
+        c.prune_stale_peers(30_001.0);
+        assert!(c.peers.is_empty());
+
+        peer(&mut c, "a");
+        peer(&mut c, "b");
+        c.set_peer_timeout(1_000.0).unwrap();
+        c.prune_stale_peers(500.0);
+        assert_eq!(c.peers.len(), 2);
+        c.prune_stale_peers(1_500.0);
+        assert!(c.peers.is_empty());
+    }
+
+    #[test]
+    fn invalid_peer_timeout_is_rejected() {
+        let mut c = CanvasInner::for_test();
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(c.set_peer_timeout(bad).is_err());
+        }
+        peer(&mut c, "a");
+        c.prune_stale_peers(1.0);
+        assert_eq!(c.peers.len(), 1);
+    }
+
+    #[test]
+    fn hidden_elements_are_not_outlined() {
+        let mut c = CanvasInner::for_test();
+        let mut hidden = rect(2);
+        hidden.hidden = true;
+        let mut group = rect(3);
+        group.shape = Shape::Group {
+            children: vec![rect(4)],
+        };
+        c.elements = vec![rect(1), hidden, group];
+        assert!(c.visible_element(1).is_some());
+        assert!(c.visible_element(2).is_none());
+        assert!(c.visible_element(4).is_some());
+        assert!(c.visible_element(99).is_none());
+
+        // 숨긴 레이어의 그룹 자식도 제외
+        assert!(c.set_layer_visible(0, false));
+        assert!(c.visible_element(1).is_none());
+        assert!(c.visible_element(4).is_none());
+    }
+}
@@ -0,0 +1,452 @@
+use crate::models::Element;
+use crate::CanvasInner;
+
+// ===== 비슷한 요소 선택 / 쿼리 선택 =====
+//
+// 쿼리 문법 (예: `kind == rect && width > 3`):
+//   expr   := or
+//   or     := and ("||" and)*
+//   and    := unary ("&&" unary)*
+//   unary  := "!" unary | "(" expr ")" | field op value
+//   field  := kind | color | width | layer | id
+//   op     := == | != | < | <= | > | >=   (문자열 필드는 == / != 만)
+//   value  := 숫자 | "문자열" | 'rect', '#ff0000' 같은 단어
+
+/// 쿼리에서 비교할 수 있는 요소 속성
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Kind,
+    Color,
+    Width,
+    Layer,
+    Id,
+}
+
+impl Field {
+    fn parse(s: &str) -> Option<Field> {
+        match s {
+            "kind" => Some(Field::Kind),
+            "color" => Some(Field::Color),
+            "width" => Some(Field::Width),
+            "layer" => Some(Field::Layer),
+            "id" => Some(Field::Id),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Field::Width | Field::Layer | Field::Id)
+    }
+
+    /// 요소의 속성값
+    fn value(&self, elem: &Element) -> Value {
+        match self {
+            Field::Kind => Value::Str(elem.shape.kind().to_string()),
+            Field::Color => Value::Str(elem.style.color.to_ascii_lowercase()),
+            Field::Width => Value::Num(elem.style.width),
+            Field::Layer => Value::Num(elem.layer as f64),
+            Field::Id => Value::Num(elem.id as f64),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone)]
+enum Value {
+    Num(f64),
+    Str(String),
+}
+
+/// 파싱된 쿼리 식
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Cmp(Field, Op, Value),
+}
+
+impl Expr {
+    fn eval(&self, elem: &Element) -> bool {
+        match self {
+            Expr::And(a, b) => a.eval(elem) && b.eval(elem),
+            Expr::Or(a, b) => a.eval(elem) || b.eval(elem),
+            Expr::Not(a) => !a.eval(elem),
+            Expr::Cmp(field, op, value) => compare(&field.value(elem), *op, value),
+        }
+    }
+}
+
+/// 두 값 비교 (타입이 다르면 false)
+fn compare(a: &Value, op: Op, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Num(a), Value::Num(b)) => match op {
+            Op::Eq => (a - b).abs() < 1e-9,
+            Op::Ne => (a - b).abs() >= 1e-9,
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+        },
+        (Value::Str(a), Value::Str(b)) => match op {
+            Op::Ne => a != b,
+            _ => a == b,
+        },
+        _ => false,
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Num(f64),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+/// 쿼리 문자열 토큰화
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '=' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Eq));
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '<' | '>' => {
+                let eq = next == Some('=');
+                tokens.push(Token::Op(match (c, eq) {
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('>', false) => Op::Gt,
+                    _ => Op::Ge,
+                }));
+                i += if eq { 2 } else { 1 };
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| "unterminated string in query".to_string())?;
+                tokens.push(Token::Str(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '#' | '.' | '-'))
+                {
+                    i += 1;
+                }
+                if start == i {
+                    return Err(format!("unexpected '{}' in query", c));
+                }
+                let word: String = chars[start..i].iter().collect();
+                tokens.push(match word.parse::<f64>() {
+                    Ok(n) => Token::Num(n),
+                    Err(_) => Token::Word(word),
+                });
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// 재귀 하강 파서
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = Expr::And(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("expected ')' in query".to_string()),
+                }
+            }
+            Some(Token::Word(name)) => {
+                let field = Field::parse(&name)
+                    .ok_or_else(|| format!("unknown field in query: {}", name))?;
+                let Some(Token::Op(op)) = self.next() else {
+                    return Err(format!("expected comparison after '{}'", name));
+                };
+                let value = match self.next() {
+                    Some(Token::Num(n)) if field.is_numeric() => Value::Num(n),
+                    Some(Token::Word(s) | Token::Str(s)) if !field.is_numeric() => {
+                        Value::Str(s.to_ascii_lowercase())
+                    }
+                    _ => return Err(format!("invalid value for '{}'", name)),
+                };
+                if !field.is_numeric() && !matches!(op, Op::Eq | Op::Ne) {
+                    return Err(format!("'{}' only supports == and !=", name));
+                }
+                Ok(Expr::Cmp(field, op, value))
+            }
+            _ => Err("expected field, '!' or '(' in query".to_string()),
+        }
+    }
+}
+
+/// 쿼리 문자열 파싱
+fn parse_query(src: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+    };
+    let expr = parser.or()?;
+    if parser.pos < parser.tokens.len() {
+        return Err("unexpected trailing input in query".to_string());
+    }
+    Ok(expr)
+}
+
+impl CanvasInner {
+    /// 조건에 맞는 선택 가능 요소로 선택 교체 → 선택된 개수
+    fn select_where(&mut self, pred: impl Fn(&Element) -> bool) -> usize {
+        self.selected_ids = self
+            .pickable()
+            .into_iter()
+            .filter(|e| pred(e))
+            .map(|e| e.id)
+            .collect();
+        self.last_duplicate = None;
+        self.needs_render = true;
+        self.selected_ids.len()
+    }
+
+    /// 선택 요소와 같은 속성(attr: "color" | "width" | "kind")의 요소를 모두 선택.
+    /// 선택이 없으면 아무것도 하지 않음 → 선택된 개수
+    pub(crate) fn select_similar(&mut self, attr: &str) -> Result<usize, String> {
+        let field = match attr {
+            "color" => Field::Color,
+            "width" => Field::Width,
+            "kind" => Field::Kind,
+            _ => return Err(format!("unknown attribute: {}", attr)),
+        };
+        let targets: Vec<Value> = self
+            .scope()
+            .iter()
+            .filter(|e| self.selected_ids.contains(&e.id))
+            .map(|e| field.value(e))
+            .collect();
+        if targets.is_empty() {
+            return Ok(0);
+        }
+        Ok(self.select_where(|e| {
+            let v = field.value(e);
+            targets.iter().any(|t| compare(&v, Op::Eq, t))
+        }))
+    }
+
+    /// 쿼리 식에 맞는 요소를 모두 선택 → 선택된 개수
+    pub(crate) fn select_by_query(&mut self, query: &str) -> Result<usize, String> {
+        let expr = parse_query(query)?;
+        Ok(self.select_where(|e| expr.eval(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Shape, Style};
+
+    fn elem(id: u32, shape: Shape, color: &str, width: f64) -> Element {
+        Element {
+            id,
+            shape,
+            style: Style {
+                color: color.to_string(),
+                width,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn canvas() -> CanvasInner {
+        let rect = Shape::Rect {
+            x: 0.0,
+            y: 0.0,
+            w: 10.0,
+            h: 10.0,
+        };
+        let mut c = CanvasInner::for_test();
+        c.elements = vec![
+            elem(1, rect.clone(), "#FF0000", 2.0),
+            elem(2, rect, "#00ff00", 5.0),
+            elem(
+                3,
+                Shape::Circle {
+                    cx: 0.0,
+                    cy: 0.0,
+                    r: 5.0,
+                },
+                "#ff0000",
+                5.0,
+            ),
+            elem(
+                4,
+                Shape::Line {
+                    x1: 0.0,
+                    y1: 0.0,
+                    x2: 10.0,
+                    y2: 0.0,
+                },
+                "#0000ff",
+                1.0,
+            ),
+        ];
+        c
+    }
+
+    /// 쿼리로 선택된 ID (정렬)
+    fn query(src: &str) -> Result<Vec<u32>, String> {
+        let mut c = canvas();
+        let count = c.select_by_query(src)?;
+        let mut ids: Vec<u32> = c.selected_ids.into_iter().collect();
+        ids.sort();
+        assert_eq!(count, ids.len());
+        Ok(ids)
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        assert_eq!(
+            query("kind == line || kind == rect && width > 3"),
+            Ok(vec![2, 4])
+        );
+        assert_eq!(
+            query("(kind == line || kind == rect) && width > 3"),
+            Ok(vec![2])
+        );
+        assert_eq!(query("id >= 2 && id <= 3 || id == 1"), Ok(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn not_applies_to_the_next_term() {
+        assert_eq!(query("!(kind == rect)"), Ok(vec![3, 4]));
+        assert_eq!(query("!kind == rect && width > 1"), Ok(vec![3]));
+        assert_eq!(query("!!(width < 2)"), Ok(vec![4]));
+    }
+
+    #[test]
+    fn colors_compare_case_insensitively() {
+        assert_eq!(query("color == '#FF0000'"), Ok(vec![1, 3]));
+        assert_eq!(query("color == \"#ff0000\""), Ok(vec![1, 3]));
+        assert_eq!(query("color != #Ff0000"), Ok(vec![2, 4]));
+    }
+
+    #[test]
+    fn invalid_queries_report_errors() {
+        assert_eq!(
+            query("color < red"),
+            Err("'color' only supports == and !=".to_string())
+        );
+        assert_eq!(
+            query("kind == \"rect"),
+            Err("unterminated string in query".to_string())
+        );
+        assert_eq!(
+            query("kind == rect)"),
+            Err("unexpected trailing input in query".to_string())
+        );
+        assert_eq!(
+            query("(kind == rect"),
+            Err("expected ')' in query".to_string())
+        );
+        assert_eq!(
+            query("width == wide"),
+            Err("invalid value for 'width'".to_string())
+        );
+        assert_eq!(
+            query("size > 1"),
+            Err("unknown field in query: size".to_string())
+        );
+    }
+
+    #[test]
+    fn select_similar_matches_selected_attributes() {
+        let mut c = canvas();
+        assert_eq!(c.select_similar("color"), Ok(0));
+        c.selected_ids.insert(1);
+        assert_eq!(c.select_similar("color"), Ok(2));
+        assert!(c.selected_ids.contains(&3));
+        assert!(c.select_similar("shape").is_err());
+    }
+}
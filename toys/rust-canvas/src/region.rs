@@ -0,0 +1,416 @@
+use crate::models::{BoundingBox, Element, Point, Shape};
+
+// ===== 영역 선택 기하 (올가미 / 러버밴드) =====
+
+/// 원 윤곽을 근사하는 다각형 변 개수
+const CIRCLE_SEGMENTS: usize = 64;
+
+/// 영역 선택 판정 방식
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum RegionMode {
+    /// 요소 전체가 영역 안에 있어야 선택
+    Contain,
+    /// 요소의 선이 영역에 닿기만 해도 선택
+    Intersect,
+}
+
+impl RegionMode {
+    pub(crate) fn parse(s: &str) -> Option<RegionMode> {
+        match s {
+            "contain" => Some(RegionMode::Contain),
+            "intersect" => Some(RegionMode::Intersect),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RegionMode::Contain => "contain",
+            RegionMode::Intersect => "intersect",
+        }
+    }
+}
+
+/// 러버밴드 판정 방식 설정
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum RubberBandMode {
+    Contain,
+    Intersect,
+    /// 왼쪽→오른쪽 드래그는 포함, 오른쪽→왼쪽은 교차
+    Direction,
+}
+
+impl RubberBandMode {
+    pub(crate) fn parse(s: &str) -> Option<RubberBandMode> {
+        match s {
+            "contain" => Some(RubberBandMode::Contain),
+            "intersect" => Some(RubberBandMode::Intersect),
+            "direction" => Some(RubberBandMode::Direction),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            RubberBandMode::Contain => "contain",
+            RubberBandMode::Intersect => "intersect",
+            RubberBandMode::Direction => "direction",
+        }
+    }
+
+    /// 드래그 방향을 반영한 실제 판정 방식
+    pub(crate) fn resolve(&self, left_to_right: bool) -> RegionMode {
+        match self {
+            RubberBandMode::Contain => RegionMode::Contain,
+            RubberBandMode::Intersect => RegionMode::Intersect,
+            RubberBandMode::Direction if left_to_right => RegionMode::Contain,
+            RubberBandMode::Direction => RegionMode::Intersect,
+        }
+    }
+}
+
+/// 선택 영역 (월드 좌표의 닫힌 다각형)
+pub(crate) struct Region {
+    points: Vec<Point>,
+    bounds: BoundingBox,
+}
+
+impl Region {
+    /// 꼭짓점 목록으로 다각형 영역 생성 (3개 미만이면 None)
+    pub(crate) fn polygon(points: Vec<Point>) -> Option<Region> {
+        if points.len() < 3 {
+            return None;
+        }
+        let bounds = points_bounds(&points)?;
+        Some(Region { points, bounds })
+    }
+
+    /// 사각형 영역 생성
+    pub(crate) fn rect(bb: &BoundingBox) -> Region {
+        Region {
+            points: vec![
+                Point {
+                    x: bb.min_x,
+                    y: bb.min_y,
+                },
+                Point {
+                    x: bb.max_x,
+                    y: bb.min_y,
+                },
+                Point {
+                    x: bb.max_x,
+                    y: bb.max_y,
+                },
+                Point {
+                    x: bb.min_x,
+                    y: bb.max_y,
+                },
+            ],
+            bounds: bb.clone(),
+        }
+    }
+
+    /// 다각형의 변 (마지막 → 첫 꼭짓점 포함)
+    fn edges(&self) -> impl Iterator<Item = (&Point, &Point)> {
+        let n = self.points.len();
+        (0..n).map(move |i| (&self.points[i], &self.points[(i + 1) % n]))
+    }
+
+    /// 점이 영역 안에 있는지 (짝홀 규칙)
+    fn contains_point(&self, p: &Point) -> bool {
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            if (a.y > p.y) != (b.y > p.y) {
+                let x = a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if p.x < x {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// 선분이 영역 경계와 만나는지
+    fn crosses(&self, a: &Point, b: &Point) -> bool {
+        self.edges().any(|(c, d)| segments_intersect(a, b, c, d))
+    }
+
+    /// 요소가 이 영역으로 선택되는지 (실제 기하 윤곽 기준)
+    pub(crate) fn selects(&self, elem: &Element, mode: RegionMode) -> bool {
+        let Some(bb) = elem.geometry_bounds() else {
+            return false;
+        };
+        if !self.bounds.intersects(&bb) {
+            return false;
+        }
+
+        let mut lines = Vec::new();
+        outlines(elem, &mut lines);
+        let segments = || {
+            lines
+                .iter()
+                .flat_map(|line| line.windows(2).map(|w| (&w[0], &w[1])))
+        };
+
+        match mode {
+            RegionMode::Contain => {
+                !lines.is_empty()
+                    && lines.iter().flatten().all(|p| self.contains_point(p))
+                    && !segments().any(|(a, b)| self.crosses(a, b))
+            }
+            RegionMode::Intersect => {
+                lines.iter().flatten().any(|p| self.contains_point(p))
+                    || segments().any(|(a, b)| self.crosses(a, b))
+            }
+        }
+    }
+}
+
+/// 점 목록의 바운딩 박스
+fn points_bounds(points: &[Point]) -> Option<BoundingBox> {
+    let first = points.first()?;
+    let mut bb = BoundingBox {
+        min_x: first.x,
+        min_y: first.y,
+        max_x: first.x,
+        max_y: first.y,
+    };
+    for p in points {
+        bb.min_x = bb.min_x.min(p.x);
+        bb.min_y = bb.min_y.min(p.y);
+        bb.max_x = bb.max_x.max(p.x);
+        bb.max_y = bb.max_y.max(p.y);
+    }
+    Some(bb)
+}
+
+/// 요소의 기하 윤곽을 폴리라인으로 (원은 다각형 근사, 숨긴 자식 제외)
+fn outlines(elem: &Element, out: &mut Vec<Vec<Point>>) {
+    match &elem.shape {
+        Shape::Freehand { points } => {
+            if !points.is_empty() {
+                out.push(points.clone());
+            }
+        }
+        Shape::Line { x1, y1, x2, y2 } => {
+            out.push(vec![Point { x: *x1, y: *y1 }, Point { x: *x2, y: *y2 }]);
+        }
+        Shape::Rect { x, y, w, h } => {
+            out.push(vec![
+                Point { x: *x, y: *y },
+                Point { x: x + w, y: *y },
+                Point { x: x + w, y: y + h },
+                Point { x: *x, y: y + h },
+                Point { x: *x, y: *y },
+            ]);
+        }
+        Shape::Circle { cx, cy, r } => {
+            out.push(
+                (0..=CIRCLE_SEGMENTS)
+                    .map(|i| {
+                        let t = i as f64 / CIRCLE_SEGMENTS as f64 * std::f64::consts::TAU;
+                        Point {
+                            x: cx + r * t.cos(),
+                            y: cy + r * t.sin(),
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        Shape::Group { children } => {
+            for child in children.iter().filter(|c| !c.hidden) {
+                outlines(child, out);
+            }
+        }
+    }
+}
+
+/// 세 점의 방향 (외적 부호)
+fn orientation(a: &Point, b: &Point, c: &Point) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// 세 점이 한 직선 위일 때 c가 선분 ab 범위 안에 있는지
+fn on_segment(a: &Point, b: &Point, c: &Point) -> bool {
+    c.x >= a.x.min(b.x) && c.x <= a.x.max(b.x) && c.y >= a.y.min(b.y) && c.y <= a.y.max(b.y)
+}
+
+/// 선분 ab와 cd가 만나는지 (끝점 접촉, 한 직선 위 겹침 포함)
+pub(crate) fn segments_intersect(a: &Point, b: &Point, c: &Point, d: &Point) -> bool {
+    let d1 = orientation(c, d, a);
+    let d2 = orientation(c, d, b);
+    let d3 = orientation(a, b, c);
+    let d4 = orientation(a, b, d);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+    (d1 == 0.0 && on_segment(c, d, a))
+        || (d2 == 0.0 && on_segment(c, d, b))
+        || (d3 == 0.0 && on_segment(a, b, c))
+        || (d4 == 0.0 && on_segment(a, b, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Style;
+
+    fn pt(x: f64, y: f64) -> Point {
+        Point { x, y }
+    }
+
+    fn elem(shape: Shape) -> Element {
+        Element {
+            id: 1,
+            shape,
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Element {
+        elem(Shape::Line { x1, y1, x2, y2 })
+    }
+
+    fn circle(cx: f64, cy: f64, r: f64) -> Element {
+        elem(Shape::Circle { cx, cy, r })
+    }
+
+    /// (0, 0)–(100, 100) 사각형 영역
+    fn square() -> Region {
+        Region::polygon(vec![
+            pt(0.0, 0.0),
+            pt(100.0, 0.0),
+            pt(100.0, 100.0),
+            pt(0.0, 100.0),
+        ])
+        .unwrap()
+    }
+
+    fn selects(region: &Region, e: &Element) -> (bool, bool) {
+        (
+            region.selects(e, RegionMode::Contain),
+            region.selects(e, RegionMode::Intersect),
+        )
+    }
+
+    #[test]
+    fn segments_crossing_and_disjoint() {
+        let (a, b) = (pt(0.0, 0.0), pt(10.0, 10.0));
+        assert!(segments_intersect(&a, &b, &pt(0.0, 10.0), &pt(10.0, 0.0)));
+        assert!(!segments_intersect(&a, &b, &pt(0.0, 1.0), &pt(9.0, 10.0)));
+        assert!(!segments_intersect(&a, &b, &pt(20.0, 0.0), &pt(11.0, 9.0)));
+    }
+
+    #[test]
+    fn segments_touching_endpoints() {
+        let (a, b) = (pt(0.0, 0.0), pt(10.0, 0.0));
+        // 끝점끼리 맞닿음
+        assert!(segments_intersect(&a, &b, &pt(10.0, 0.0), &pt(10.0, 10.0)));
+        // 한 끝점이 다른 선분 중간에 닿음
+        assert!(segments_intersect(&a, &b, &pt(5.0, 0.0), &pt(5.0, 10.0)));
+        // 한 직선 위에서 겹침 / 떨어짐
+        assert!(segments_intersect(&a, &b, &pt(5.0, 0.0), &pt(15.0, 0.0)));
+        assert!(!segments_intersect(&a, &b, &pt(11.0, 0.0), &pt(15.0, 0.0)));
+    }
+
+    #[test]
+    fn contains_point_even_odd() {
+        let region = square();
+        assert!(region.contains_point(&pt(50.0, 50.0)));
+        assert!(!region.contains_point(&pt(150.0, 50.0)));
+        assert!(!region.contains_point(&pt(-1.0, 50.0)));
+
+        // ㄱ자 오목 다각형: 파인 부분은 바깥
+        let notch = Region::polygon(vec![
+            pt(0.0, 0.0),
+            pt(100.0, 0.0),
+            pt(100.0, 100.0),
+            pt(50.0, 100.0),
+            pt(50.0, 50.0),
+            pt(0.0, 50.0),
+        ])
+        .unwrap();
+        assert!(notch.contains_point(&pt(75.0, 75.0)));
+        assert!(notch.contains_point(&pt(25.0, 25.0)));
+        assert!(!notch.contains_point(&pt(25.0, 75.0)));
+    }
+
+    #[test]
+    fn diagonal_line_crossing_region() {
+        // 양 끝점이 모두 밖이지만 영역을 가로지름
+        let region = square();
+        assert_eq!(
+            selects(&region, &line(-20.0, -20.0, 120.0, 120.0)),
+            (false, true)
+        );
+        assert_eq!(
+            selects(&region, &line(-20.0, 120.0, 120.0, -20.0)),
+            (false, true)
+        );
+        // 바운딩 박스는 겹치지만 선은 닿지 않음
+        assert_eq!(
+            selects(&region, &line(90.0, -20.0, 120.0, 10.0)),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn enclosed_rect_is_contained() {
+        let region = square();
+        let inner = elem(Shape::Rect {
+            x: 20.0,
+            y: 20.0,
+            w: 40.0,
+            h: 40.0,
+        });
+        assert_eq!(selects(&region, &inner), (true, true));
+
+        let straddling = elem(Shape::Rect {
+            x: 80.0,
+            y: 20.0,
+            w: 40.0,
+            h: 40.0,
+        });
+        assert_eq!(selects(&region, &straddling), (false, true));
+    }
+
+    #[test]
+    fn circle_uses_outline() {
+        let region = square();
+        assert_eq!(selects(&region, &circle(50.0, 50.0, 10.0)), (true, true));
+        assert_eq!(selects(&region, &circle(100.0, 50.0, 10.0)), (false, true));
+        // 영역을 통째로 감싸는 원: 윤곽이 영역에 닿지 않음
+        assert_eq!(selects(&region, &circle(50.0, 50.0, 200.0)), (false, false));
+        // 바운딩 박스 모서리만 겹치는 원
+        assert_eq!(
+            selects(&region, &circle(110.0, 110.0, 12.0)),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn touching_endpoint_intersects_but_is_not_contained() {
+        let region = square();
+        assert_eq!(
+            selects(&region, &line(100.0, 100.0, 150.0, 150.0)),
+            (false, true)
+        );
+        assert_eq!(
+            selects(&region, &line(100.0, 50.0, 150.0, 50.0)),
+            (false, true)
+        );
+        assert_eq!(
+            selects(&region, &line(101.0, 50.0, 150.0, 50.0)),
+            (false, false)
+        );
+    }
+}
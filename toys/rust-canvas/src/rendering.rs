@@ -1,5 +1,6 @@
-use crate::models::{Element, Shape};
-use crate::{CanvasInner, ToolMode};
+use crate::layers::layer_index;
+use crate::models::{Element, Shape, Style};
+use crate::CanvasInner;
 
 impl CanvasInner {
     /// 캔버스 클리어 (배경만, 변환 없이)
@@ -10,6 +11,9 @@ impl CanvasInner {
 
     /// 단일 요소 그리기 (Shape 별 디스패치)
     pub(crate) fn draw_element(&self, element: &Element) {
+        if element.hidden {
+            return;
+        }
         let style = &element.style;
         self.ctx.set_stroke_style_str(&style.color);
         self.ctx.set_line_width(style.width);
@@ -42,6 +46,11 @@ impl CanvasInner {
                 let _ = self.ctx.arc(*cx, *cy, *r, 0.0, std::f64::consts::PI * 2.0);
                 self.ctx.stroke();
             }
+            Shape::Group { children } => {
+                for child in children {
+                    self.draw_element(child);
+                }
+            }
         }
     }
 
@@ -51,44 +60,25 @@ impl CanvasInner {
             return;
         }
 
+        // 확정 시와 같은 제약(Shift/Alt)을 적용한 도형
+        let Some(shape) = self.constrained_shape() else {
+            return;
+        };
+        let preview = Element {
+            id: 0,
+            shape,
+            style: Style {
+                color: self.color.clone(),
+                width: self.line_width,
+            },
+            layer: self.active_layer,
+            locked: false,
+            hidden: false,
+        };
+
         self.ctx.save();
         self.ctx.set_global_alpha(0.6);
-        self.ctx.set_stroke_style_str(&self.color);
-        self.ctx.set_line_width(self.line_width);
-
-        match self.tool_mode {
-            ToolMode::Line => {
-                self.ctx.begin_path();
-                self.ctx.move_to(self.shape_start_x, self.shape_start_y);
-                self.ctx.line_to(self.shape_end_x, self.shape_end_y);
-                self.ctx.stroke();
-            }
-            ToolMode::Rectangle => {
-                let x = self.shape_start_x.min(self.shape_end_x);
-                let y = self.shape_start_y.min(self.shape_end_y);
-                let w = (self.shape_end_x - self.shape_start_x).abs();
-                let h = (self.shape_end_y - self.shape_start_y).abs();
-                self.ctx.begin_path();
-                self.ctx.rect(x, y, w, h);
-                self.ctx.stroke();
-            }
-            ToolMode::Circle => {
-                let dx = self.shape_end_x - self.shape_start_x;
-                let dy = self.shape_end_y - self.shape_start_y;
-                let r = (dx * dx + dy * dy).sqrt();
-                self.ctx.begin_path();
-                let _ = self.ctx.arc(
-                    self.shape_start_x,
-                    self.shape_start_y,
-                    r,
-                    0.0,
-                    std::f64::consts::PI * 2.0,
-                );
-                self.ctx.stroke();
-            }
-            _ => {}
-        }
-
+        self.draw_element(&preview);
         self.ctx.restore();
     }
 
@@ -132,32 +122,62 @@ impl CanvasInner {
         let _ = self.ctx.translate(self.pan_x, self.pan_y);
         let _ = self.ctx.scale(self.zoom, self.zoom);
 
-        // 저장된 모든 요소 그리기 (월드 스페이스)
-        for element in &self.elements {
-            self.draw_element(element);
+        // 배경 그리드 (월드 스페이스)
+        self.draw_grid();
+
+        // 저장된 모든 요소를 레이어 순으로 그리기 (월드 스페이스, 재생 중이면 재생 문서)
+        let (elements, layers) = match &self.replay {
+            Some(replay) => (&replay.elements, &replay.layers),
+            None => (&self.elements, &self.layers),
+        };
+        for (li, layer) in layers.iter().enumerate() {
+            if !layer.visible {
+                continue;
+            }
+            self.ctx.save();
+            self.ctx.set_global_alpha(layer.opacity);
+            for element in elements.iter().filter(|e| layer_index(layers, e.layer) == li) {
+                self.draw_element(element);
+            }
+            self.ctx.restore();
         }
 
-        // 현재 그리는 중인 요소 (월드 스페이스)
-        if let Some(ref current) = self.current_element {
-            self.draw_element(current);
-        }
+        // 편집 오버레이는 라이브 문서 기준이라 재생 중에는 그리지 않음
+        let live = self.replay.is_none();
 
-        // 도형 프리뷰 (월드 스페이스)
-        self.draw_shape_preview();
+        if live {
+            // 현재 그리는 중인 요소 (월드 스페이스)
+            if let Some(ref current) = self.current_element {
+                self.draw_element(current);
+            }
+
+            // 도형 프리뷰 (월드 스페이스)
+            self.draw_shape_preview();
 
-        // 선택 하이라이트 (월드 스페이스)
-        self.draw_selection_highlight();
+            // 스냅 가이드 (월드 스페이스)
+            self.draw_snap_guides();
+
+            // 들어가 있는 그룹 범위 + 선택 하이라이트 (월드 스페이스)
+            self.draw_group_scope();
+            self.draw_selection_highlight();
+
+            // 원격 참여자 선택/커서 (월드 스페이스)
+            self.draw_peers();
+        }
 
         // 3. 카메라 변환 해제
         self.ctx.restore();
 
         // 4. 스크린 스페이스 UI
-        // 러버밴드 (스크린 스페이스)
-        self.draw_rubber_band();
-
-        // 커서 미리보기 (스크린 스페이스)
-        if self.show_cursor {
-            self.draw_cursor_preview();
+        if live {
+            // 러버밴드 / 올가미 (스크린 스페이스)
+            self.draw_rubber_band();
+            self.draw_lasso();
+
+            // 커서 미리보기 (스크린 스페이스)
+            if self.show_cursor {
+                self.draw_cursor_preview();
+            }
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::region::RegionMode;
 use crate::CanvasInner;
 
 // ===== 선택 하이라이트 렌더링 =====
@@ -17,7 +18,7 @@ impl CanvasInner {
 
         let padding = 6.0;
 
-        for elem in &self.elements {
+        for elem in self.scope() {
             if !self.selected_ids.contains(&elem.id) {
                 continue;
             }
@@ -53,8 +54,10 @@ impl CanvasInner {
         self.ctx.set_fill_style_str("rgba(59, 130, 246, 0.1)");
         self.ctx.fill_rect(x, y, w, h);
 
-        // 파란 점선 테두리
-        let _ = self.ctx.set_line_dash(&self.dash_selection);
+        // 파란 테두리 (포함 판정은 실선, 교차 판정은 점선)
+        if self.rubber_band_region_mode() == RegionMode::Intersect {
+            let _ = self.ctx.set_line_dash(&self.dash_selection);
+        }
         self.ctx.set_stroke_style_str("#3b82f6");
         self.ctx.set_line_width(1.0);
         self.ctx.begin_path();
@@ -63,4 +66,35 @@ impl CanvasInner {
 
         self.ctx.restore();
     }
+
+    /// 올가미 (자유 곡선) 영역 그리기
+    pub(crate) fn draw_lasso(&self) {
+        if !self.is_lasso || self.lasso_points.len() < 2 {
+            return;
+        }
+
+        self.ctx.save();
+
+        self.ctx.begin_path();
+        self.ctx
+            .move_to(self.lasso_points[0].x, self.lasso_points[0].y);
+        for p in self.lasso_points.iter().skip(1) {
+            self.ctx.line_to(p.x, p.y);
+        }
+        self.ctx.close_path();
+
+        // 반투명 파란 배경
+        self.ctx.set_fill_style_str("rgba(59, 130, 246, 0.1)");
+        self.ctx.fill();
+
+        // 파란 테두리 (포함 판정은 실선, 교차 판정은 점선)
+        if self.lasso_mode == RegionMode::Intersect {
+            let _ = self.ctx.set_line_dash(&self.dash_selection);
+        }
+        self.ctx.set_stroke_style_str("#3b82f6");
+        self.ctx.set_line_width(1.0);
+        self.ctx.stroke();
+
+        self.ctx.restore();
+    }
 }
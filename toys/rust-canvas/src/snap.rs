@@ -0,0 +1,468 @@
+use crate::models::BoundingBox;
+use crate::CanvasInner;
+
+// ===== 오브젝트 스냅 / 스마트 가이드 =====
+
+/// 스냅이 걸리는 거리 (스크린 px, 줌과 무관하게 일정)
+const SNAP_THRESHOLD_PX: f64 = 6.0;
+
+/// 같은 위치로 간주하는 허용 오차 (월드 단위)
+const EPSILON: f64 = 1e-6;
+
+/// 렌더링용 가이드 (월드 좌표 선분)
+pub(crate) enum Guide {
+    /// 정렬선 (모서리/중심이 맞는 위치)
+    Align { x1: f64, y1: f64, x2: f64, y2: f64 },
+    /// 같은 간격 힌트 (양 끝 눈금 표시)
+    Gap { x1: f64, y1: f64, x2: f64, y2: f64 },
+}
+
+/// 한 축의 구간 (x축이면 min_x..max_x)
+#[derive(Clone, Copy)]
+struct Span {
+    lo: f64,
+    hi: f64,
+}
+
+impl Span {
+    fn mid(&self) -> f64 {
+        (self.lo + self.hi) / 2.0
+    }
+
+    fn anchors(&self) -> [f64; 3] {
+        [self.lo, self.mid(), self.hi]
+    }
+
+    fn overlaps(&self, other: &Span) -> bool {
+        self.lo <= other.hi && other.lo <= self.hi
+    }
+}
+
+/// 축 하나의 스냅 결과 (pos: 스냅 축 좌표, cross: 교차 축 좌표)
+struct AxisSnap {
+    offset: f64,
+    /// (pos, cross_lo, cross_hi)
+    lines: Vec<(f64, f64, f64)>,
+    /// (pos_lo, pos_hi, cross)
+    gaps: Vec<(f64, f64, f64)>,
+}
+
+/// 같은 간격 후보 (이동량, 표시할 간격 구간들)
+struct GapCandidate {
+    offset: f64,
+    gaps: Vec<(f64, f64)>,
+}
+
+/// 정렬 후보 중 가장 가까운 이동량
+fn best_align_offset(moving: Span, targets: &[(Span, Span)], threshold: f64) -> Option<f64> {
+    let mut best: Option<f64> = None;
+    for (span, _) in targets {
+        for t in span.anchors() {
+            for a in moving.anchors() {
+                let d = t - a;
+                if d.abs() <= threshold && best.is_none_or(|b| d.abs() < b.abs() - EPSILON) {
+                    best = Some(d);
+                }
+            }
+        }
+    }
+    best
+}
+
+/// 교차 축으로 겹치는 이웃 간격과 같은 간격이 되는 위치 후보
+fn gap_candidates(moving: Span, moving_cross: Span, targets: &[(Span, Span)]) -> Vec<GapCandidate> {
+    let mut row: Vec<Span> = targets
+        .iter()
+        .filter(|(_, cross)| cross.overlaps(&moving_cross))
+        .map(|(span, _)| *span)
+        .collect();
+    row.sort_by(|a, b| a.lo.total_cmp(&b.lo));
+
+    let width = moving.hi - moving.lo;
+    let mut candidates = Vec::new();
+    for pair in row.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let gap = b.lo - a.hi;
+        if gap <= 0.0 {
+            continue;
+        }
+        // b 뒤에 같은 간격으로
+        candidates.push(GapCandidate {
+            offset: b.hi + gap - moving.lo,
+            gaps: vec![(a.hi, b.lo), (b.hi, b.hi + gap)],
+        });
+        // a 앞에 같은 간격으로
+        candidates.push(GapCandidate {
+            offset: a.lo - gap - moving.hi,
+            gaps: vec![(a.lo - gap, a.lo), (a.hi, b.lo)],
+        });
+        // a와 b 사이 가운데
+        if gap > width {
+            let side = (gap - width) / 2.0;
+            candidates.push(GapCandidate {
+                offset: a.hi + side - moving.lo,
+                gaps: vec![(a.hi, a.hi + side), (b.lo - side, b.lo)],
+            });
+        }
+    }
+    candidates
+}
+
+/// 한 축 스냅: 정렬과 같은 간격 중 가장 가까운 것 (동률이면 정렬 우선)
+fn snap_axis(
+    moving: Span,
+    moving_cross: Span,
+    targets: &[(Span, Span)],
+    threshold: f64,
+    spacing: bool,
+) -> Option<AxisSnap> {
+    let align = best_align_offset(moving, targets, threshold);
+    let gap = if spacing {
+        gap_candidates(moving, moving_cross, targets)
+            .into_iter()
+            .filter(|c| c.offset.abs() <= threshold)
+            .min_by(|a, b| a.offset.abs().total_cmp(&b.offset.abs()))
+    } else {
+        None
+    };
+
+    let align = align.filter(|d| {
+        gap.as_ref()
+            .is_none_or(|g| d.abs() <= g.offset.abs() + EPSILON)
+    });
+    match (align, gap) {
+        (Some(d), _) => {
+            // 같은 이동량으로 맞는 모든 정렬선 수집 (같은 위치는 하나로 합침)
+            let mut lines: Vec<(f64, f64, f64)> = Vec::new();
+            for (span, cross) in targets {
+                for t in span.anchors() {
+                    if !moving.anchors().iter().any(|a| (t - a - d).abs() < EPSILON) {
+                        continue;
+                    }
+                    let lo = cross.lo.min(moving_cross.lo);
+                    let hi = cross.hi.max(moving_cross.hi);
+                    match lines.iter_mut().find(|l| (l.0 - t).abs() < EPSILON) {
+                        Some(line) => {
+                            line.1 = line.1.min(lo);
+                            line.2 = line.2.max(hi);
+                        }
+                        None => lines.push((t, lo, hi)),
+                    }
+                }
+            }
+            Some(AxisSnap {
+                offset: d,
+                lines,
+                gaps: Vec::new(),
+            })
+        }
+        (_, Some(g)) => {
+            let cross = moving_cross.mid();
+            Some(AxisSnap {
+                offset: g.offset,
+                lines: Vec::new(),
+                gaps: g.gaps.into_iter().map(|(lo, hi)| (lo, hi, cross)).collect(),
+            })
+        }
+        (None, None) => None,
+    }
+}
+
+impl CanvasInner {
+    /// 스냅 대상: 선택되지 않은 요소 중 화면에 보이는 것들의 기하 바운딩 박스
+    fn snap_targets(&self) -> Vec<BoundingBox> {
+        let view = self.visible_world_bounds();
+        self.scope()
+            .iter()
+            .filter(|e| !self.selected_ids.contains(&e.id) && self.is_rendered(e))
+            .filter_map(|e| e.geometry_bounds())
+            .filter(|bb| bb.intersects(&view))
+            .collect()
+    }
+
+    /// 박스를 다른 요소에 스냅. (x축 보정량, y축 보정량)과 가이드 반환
+    fn snap_box(
+        &self,
+        moving: &BoundingBox,
+        spacing: bool,
+    ) -> (Option<f64>, Option<f64>, Vec<Guide>) {
+        let threshold = SNAP_THRESHOLD_PX / self.zoom;
+        let targets = self.snap_targets();
+        let x_span = Span {
+            lo: moving.min_x,
+            hi: moving.max_x,
+        };
+        let y_span = Span {
+            lo: moving.min_y,
+            hi: moving.max_y,
+        };
+        let by_x: Vec<(Span, Span)> = targets
+            .iter()
+            .map(|bb| {
+                (
+                    Span {
+                        lo: bb.min_x,
+                        hi: bb.max_x,
+                    },
+                    Span {
+                        lo: bb.min_y,
+                        hi: bb.max_y,
+                    },
+                )
+            })
+            .collect();
+        let by_y: Vec<(Span, Span)> = by_x.iter().map(|(x, y)| (*y, *x)).collect();
+
+        let mut guides = Vec::new();
+        let snap_x = snap_axis(x_span, y_span, &by_x, threshold, spacing);
+        let snap_y = snap_axis(y_span, x_span, &by_y, threshold, spacing);
+
+        // 교차 축 좌표는 반대 축 스냅까지 반영된 위치로 표시
+        let dx = snap_x.as_ref().map_or(0.0, |s| s.offset);
+        let dy = snap_y.as_ref().map_or(0.0, |s| s.offset);
+        if let Some(s) = &snap_x {
+            for &(x, lo, hi) in &s.lines {
+                guides.push(Guide::Align {
+                    x1: x,
+                    y1: lo.min(moving.min_y + dy),
+                    x2: x,
+                    y2: hi.max(moving.max_y + dy),
+                });
+            }
+            for &(lo, hi, y) in &s.gaps {
+                guides.push(Guide::Gap {
+                    x1: lo,
+                    y1: y + dy,
+                    x2: hi,
+                    y2: y + dy,
+                });
+            }
+        }
+        if let Some(s) = &snap_y {
+            for &(y, lo, hi) in &s.lines {
+                guides.push(Guide::Align {
+                    x1: lo.min(moving.min_x + dx),
+                    y1: y,
+                    x2: hi.max(moving.max_x + dx),
+                    y2: y,
+                });
+            }
+            for &(lo, hi, x) in &s.gaps {
+                guides.push(Guide::Gap {
+                    x1: x + dx,
+                    y1: lo,
+                    x2: x + dx,
+                    y2: hi,
+                });
+            }
+        }
+
+        (snap_x.map(|s| s.offset), snap_y.map(|s| s.offset), guides)
+    }
+
+    /// 이동 중인 선택 영역 스냅: 오브젝트 스냅이 없는 축은 그리드 스냅.
+    /// origin은 이동 시작 시 선택 영역, 반환값은 보정된 누적 이동량
+    pub(crate) fn snap_move(&mut self, origin: &BoundingBox, dx: f64, dy: f64) -> (f64, f64) {
+        let moved = BoundingBox {
+            min_x: origin.min_x + dx,
+            min_y: origin.min_y + dy,
+            max_x: origin.max_x + dx,
+            max_y: origin.max_y + dy,
+        };
+        let (gx, gy) = self.snap_to_grid(moved.min_x, moved.min_y);
+        let (ox, oy, guides) = if self.object_snap {
+            self.snap_box(&moved, true)
+        } else {
+            (None, None, Vec::new())
+        };
+        self.snap_guides = guides;
+        (
+            dx + ox.unwrap_or(gx - moved.min_x),
+            dy + oy.unwrap_or(gy - moved.min_y),
+        )
+    }
+
+    /// 도형 그리기 좌표 스냅: 다른 요소의 모서리/중심, 없으면 그리드
+    pub(crate) fn snap_point(&mut self, x: f64, y: f64) -> (f64, f64) {
+        let (gx, gy) = self.snap_to_grid(x, y);
+        let (ox, oy, guides) = if self.object_snap {
+            let point = BoundingBox {
+                min_x: x,
+                min_y: y,
+                max_x: x,
+                max_y: y,
+            };
+            self.snap_box(&point, false)
+        } else {
+            (None, None, Vec::new())
+        };
+        self.snap_guides = guides;
+        (ox.map_or(gx, |d| x + d), oy.map_or(gy, |d| y + d))
+    }
+
+    /// 가이드 제거 (인터랙션 종료 시)
+    pub(crate) fn clear_snap_guides(&mut self) {
+        if !self.snap_guides.is_empty() {
+            self.snap_guides.clear();
+            self.needs_render = true;
+        }
+    }
+
+    /// 오브젝트 스냅 on/off
+    pub(crate) fn set_object_snap(&mut self, enabled: bool) {
+        self.object_snap = enabled;
+        if !enabled {
+            self.clear_snap_guides();
+        }
+    }
+
+    /// 스냅 가이드 그리기 (월드 스페이스, 줌과 무관하게 1px)
+    pub(crate) fn draw_snap_guides(&self) {
+        if self.snap_guides.is_empty() {
+            return;
+        }
+        let px = 1.0 / self.zoom;
+        let tick = 4.0 * px;
+
+        self.ctx.save();
+        self.ctx.set_stroke_style_str("#ff3b8b");
+        self.ctx.set_line_width(px);
+        self.ctx.begin_path();
+        for guide in &self.snap_guides {
+            match *guide {
+                Guide::Align { x1, y1, x2, y2 } => {
+                    self.ctx.move_to(x1, y1);
+                    self.ctx.line_to(x2, y2);
+                }
+                Guide::Gap { x1, y1, x2, y2 } => {
+                    self.ctx.move_to(x1, y1);
+                    self.ctx.line_to(x2, y2);
+                    // 양 끝 눈금 (선분에 수직)
+                    let (nx, ny) = if x1 == x2 { (tick, 0.0) } else { (0.0, tick) };
+                    for (x, y) in [(x1, y1), (x2, y2)] {
+                        self.ctx.move_to(x - nx, y - ny);
+                        self.ctx.line_to(x + nx, y + ny);
+                    }
+                }
+            }
+        }
+        self.ctx.stroke();
+        self.ctx.restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Element, Shape, Style};
+
+    fn rect(id: u32, x: f64, y: f64, w: f64, h: f64) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect { x, y, w, h },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 2.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn bbox(x: f64, y: f64, w: f64, h: f64) -> BoundingBox {
+        BoundingBox {
+            min_x: x,
+            min_y: y,
+            max_x: x + w,
+            max_y: y + h,
+        }
+    }
+
+    /// x 100..150, y 100..150 사각형 하나
+    fn canvas() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        c.elements.push(rect(1, 100.0, 100.0, 50.0, 50.0));
+        c
+    }
+
+    /// 세로 정렬선이 x에 있는지
+    fn has_vertical_line(c: &CanvasInner, x: f64) -> bool {
+        c.snap_guides
+            .iter()
+            .any(|g| matches!(*g, Guide::Align { x1, x2, .. } if x1 == x && x2 == x))
+    }
+
+    #[test]
+    fn threshold_is_constant_in_screen_pixels() {
+        // 이동 박스 x 0..20, y 300..320 (y축으로는 정렬 대상 없음)
+        let origin = bbox(0.0, 300.0, 20.0, 20.0);
+        let mut c = canvas();
+        assert_eq!(c.snap_move(&origin, 95.0, 0.0), (100.0, 0.0));
+        assert!(has_vertical_line(&c, 100.0));
+
+        // 확대하면 월드 단위 임계값이 줄어듦 (6px / 2 = 3)
+        c.zoom = 2.0;
+        assert_eq!(c.snap_move(&origin, 95.0, 0.0), (95.0, 0.0));
+        assert!(c.snap_guides.is_empty());
+
+        // 축소하면 늘어남 (6px / 0.5 = 12): 너비 100 박스의 왼쪽 90 → 100
+        let wide = bbox(0.0, 300.0, 100.0, 20.0);
+        c.zoom = 1.0;
+        assert_eq!(c.snap_move(&wide, 90.0, 0.0), (90.0, 0.0));
+        c.zoom = 0.5;
+        assert_eq!(c.snap_move(&wide, 90.0, 0.0), (100.0, 0.0));
+    }
+
+    #[test]
+    fn aligns_edges_and_centers() {
+        let origin = bbox(0.0, 300.0, 20.0, 20.0);
+        let mut c = canvas();
+
+        // 중심 123 → 125
+        assert_eq!(c.snap_move(&origin, 113.0, 0.0), (115.0, 0.0));
+        assert!(has_vertical_line(&c, 125.0));
+
+        // 왼쪽 모서리 148 → 오른쪽 모서리 150
+        assert_eq!(c.snap_move(&origin, 148.0, 0.0), (150.0, 0.0));
+        assert!(has_vertical_line(&c, 150.0));
+
+        // 스냅을 끄면 그대로
+        c.set_object_snap(false);
+        assert_eq!(c.snap_move(&origin, 148.0, 0.0), (148.0, 0.0));
+        assert!(c.snap_guides.is_empty());
+    }
+
+    #[test]
+    fn snaps_to_equal_gap() {
+        let mut c = canvas();
+        c.elements.push(rect(2, 200.0, 100.0, 50.0, 50.0));
+
+        // 100..150, 200..250 뒤에 같은 간격 50 → 300
+        let origin = bbox(0.0, 100.0, 50.0, 50.0);
+        assert_eq!(c.snap_move(&origin, 298.0, 0.0), (300.0, 0.0));
+        let gaps: Vec<(f64, f64)> = c
+            .snap_guides
+            .iter()
+            .filter_map(|g| match *g {
+                Guide::Gap { x1, x2, .. } => Some((x1, x2)),
+                Guide::Align { .. } => None,
+            })
+            .collect();
+        assert_eq!(gaps, vec![(150.0, 200.0), (250.0, 300.0)]);
+    }
+
+    #[test]
+    fn axis_without_object_snap_falls_back_to_grid() {
+        let mut c = canvas();
+        c.grid.snap = true;
+        c.grid.spacing = 10.0;
+        let origin = bbox(0.0, 300.0, 20.0, 20.0);
+
+        // x는 오브젝트 스냅 (95 → 100), y는 그리드 (333 → 330)
+        assert_eq!(c.snap_move(&origin, 95.0, 33.0), (100.0, 30.0));
+
+        // 도형 그리기 점도 같은 규칙
+        assert_eq!(c.snap_point(148.0, 333.0), (150.0, 330.0));
+    }
+}
@@ -1,12 +1,34 @@
 use std::fmt::Write;
 
+use crate::layers::layer_index;
 use crate::models::{Element, Shape};
 use crate::CanvasInner;
 
+/// XML 속성값 이스케이프
+pub(crate) fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
 impl Element {
     /// SVG 요소 문자열 생성
-    fn to_svg(&self) -> String {
+    pub(crate) fn to_svg(&self) -> String {
+        if self.hidden {
+            return String::new();
+        }
         let s = &self.style;
+        // 색상은 붙여넣은 JSON/SVG에서 온 임의 문자열일 수 있음
+        let color = escape_xml(&s.color);
         match &self.shape {
             Shape::Freehand { points } => {
                 if points.len() < 2 {
@@ -18,27 +40,35 @@ impl Element {
                 }
                 format!(
                     r#"<path d="{}" stroke="{}" stroke-width="{}" fill="none" stroke-linecap="round" stroke-linejoin="round"/>"#,
-                    d, s.color, s.width
+                    d, color, s.width
                 )
             }
             Shape::Line { x1, y1, x2, y2 } => {
                 format!(
                     r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" stroke-linecap="round"/>"#,
-                    x1, y1, x2, y2, s.color, s.width
+                    x1, y1, x2, y2, color, s.width
                 )
             }
             Shape::Rect { x, y, w, h } => {
                 format!(
                     r#"<rect x="{}" y="{}" width="{}" height="{}" stroke="{}" stroke-width="{}" fill="none" stroke-linejoin="round"/>"#,
-                    x, y, w, h, s.color, s.width
+                    x, y, w, h, color, s.width
                 )
             }
             Shape::Circle { cx, cy, r } => {
                 format!(
                     r#"<circle cx="{}" cy="{}" r="{}" stroke="{}" stroke-width="{}" fill="none"/>"#,
-                    cx, cy, r, s.color, s.width
+                    cx, cy, r, color, s.width
                 )
             }
+            Shape::Group { children } => {
+                let mut g = String::from("<g>");
+                for child in children {
+                    g.push_str(&child.to_svg());
+                }
+                g.push_str("</g>");
+                g
+            }
         }
     }
 }
@@ -46,8 +76,16 @@ impl Element {
 impl CanvasInner {
     /// SVG 문자열 내보내기
     pub(crate) fn export_svg(&self) -> String {
-        if self.elements.is_empty() {
-            return String::from(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 800 500"><rect width="800" height="500" fill="white"/></svg>"#);
+        // 숨긴 요소와 숨긴 레이어의 요소는 제외
+        let visible: Vec<&Element> = self
+            .elements
+            .iter()
+            .filter(|e| !e.hidden && self.layers[layer_index(&self.layers, e.layer)].visible)
+            .collect();
+        if visible.is_empty() {
+            return String::from(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 800 500"><rect width="800" height="500" fill="white"/></svg>"#,
+            );
         }
 
         // 전체 컨텐츠 바운딩 박스 계산
@@ -56,7 +94,7 @@ impl CanvasInner {
         let mut max_x = f64::NEG_INFINITY;
         let mut max_y = f64::NEG_INFINITY;
 
-        for elem in &self.elements {
+        for elem in &visible {
             if let Some(bb) = elem.bounding_box() {
                 min_x = min_x.min(bb.min_x);
                 min_y = min_y.min(bb.min_y);
@@ -85,17 +123,85 @@ impl CanvasInner {
         );
         svg.push('\n');
 
-        // 요소들
-        for elem in &self.elements {
-            let s = elem.to_svg();
-            if !s.is_empty() {
-                svg.push_str("  ");
-                svg.push_str(&s);
-                svg.push('\n');
+        // 그리드 (옵션)
+        let grid = self.grid_svg(vx, vy, vw, vh);
+        if !grid.is_empty() {
+            svg.push_str("  ");
+            svg.push_str(&grid);
+            svg.push('\n');
+        }
+
+        // 레이어별 요소들 (아래 → 위)
+        for (li, layer) in self.layers.iter().enumerate() {
+            if !layer.visible {
+                continue;
+            }
+            let _ = write!(
+                svg,
+                r#"  <g id="layer-{}" data-name="{}""#,
+                layer.id,
+                escape_xml(&layer.name)
+            );
+            if layer.opacity < 1.0 {
+                let _ = write!(svg, r#" opacity="{}""#, layer.opacity);
             }
+            svg.push_str(">\n");
+            for elem in visible
+                .iter()
+                .filter(|e| layer_index(&self.layers, e.layer) == li)
+            {
+                let s = elem.to_svg();
+                if !s.is_empty() {
+                    svg.push_str("    ");
+                    svg.push_str(&s);
+                    svg.push('\n');
+                }
+            }
+            svg.push_str("  </g>\n");
         }
 
         svg.push_str("</svg>");
         svg
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+
+    #[test]
+    fn color_is_escaped() {
+        let elem = Element {
+            id: 1,
+            shape: Shape::Line {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 10.0,
+                y2: 10.0,
+            },
+            style: Style {
+                color: r#"red"/><script>alert(1)</script><x a=""#.to_string(),
+                width: 2.0,
+            },
+            layer: 0,
+            locked: false,
+            hidden: false,
+        };
+        let svg = elem.to_svg();
+        assert!(!svg.contains("<script>"), "{}", svg);
+        assert!(
+            svg.contains(r#"stroke="red&quot;/&gt;&lt;script&gt;"#),
+            "{}",
+            svg
+        );
+    }
+
+    #[test]
+    fn grid_colors_are_escaped() {
+        let mut c = crate::CanvasInner::for_test();
+        c.set_grid_config(r#"{"visible": true, "export": true, "color": "\"/><script>"}"#)
+            .unwrap();
+        let svg = c.grid_svg(0.0, 0.0, 100.0, 100.0);
+        assert!(!svg.contains("<script>"), "{}", svg);
+    }
+}
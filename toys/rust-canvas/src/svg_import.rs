@@ -0,0 +1,439 @@
+use std::collections::HashMap;
+
+use crate::models::{Element, Point, Shape, Style};
+
+// ===== SVG 가져오기 (붙여넣기용 최소 파서) =====
+//
+// line / rect / circle / polyline / polygon / path 만 지원하고 <g>는 평탄화한다.
+// 선(stroke)이 없는 도형(배경 등 채우기 전용)은 건너뛰고, transform/텍스트 등은
+// 무시하며 곡선 경로는 선분으로 근사한다.
+
+/// 곡선 하나를 근사하는 선분 개수
+const CURVE_STEPS: usize = 8;
+
+/// stroke-width가 없을 때의 선 굵기
+const DEFAULT_WIDTH: f64 = 1.0;
+
+/// SVG 시작 태그 하나 (이름 + 속성)
+struct Tag {
+    name: String,
+    attrs: HashMap<String, String>,
+}
+
+impl Tag {
+    fn num(&self, key: &str) -> f64 {
+        self.attrs
+            .get(key)
+            .and_then(|v| parse_length(v))
+            .unwrap_or(0.0)
+    }
+
+    /// stroke / stroke-width (style 속성이 우선). 선이 없으면 None
+    fn style(&self) -> Option<Style> {
+        let mut props: HashMap<String, String> = self.attrs.clone();
+        if let Some(style) = self.attrs.get("style") {
+            for decl in style.split(';') {
+                if let Some((k, v)) = decl.split_once(':') {
+                    props.insert(k.trim().to_string(), v.trim().to_string());
+                }
+            }
+        }
+        let color = props
+            .get("stroke")
+            .filter(|v| !v.is_empty() && *v != "none")?
+            .clone();
+        Some(Style {
+            color,
+            width: props
+                .get("stroke-width")
+                .and_then(|v| parse_length(v))
+                .filter(|w| *w > 0.0)
+                .unwrap_or(DEFAULT_WIDTH),
+        })
+    }
+}
+
+/// "10", "10px" 같은 길이 값 ("inf", "NaN" 등 유한하지 않은 값은 None)
+fn parse_length(s: &str) -> Option<f64> {
+    s.trim()
+        .trim_end_matches("px")
+        .trim()
+        .parse()
+        .ok()
+        .filter(|v: &f64| v.is_finite())
+}
+
+/// 문서의 시작 태그 목록 (주석/선언/<defs> 내부 제외)
+fn scan_tags(src: &str) -> Vec<Tag> {
+    let mut tags = Vec::new();
+    let mut rest = src;
+    let mut defs_depth = 0;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(body) = rest.strip_prefix("!--") {
+            rest = body.find("-->").map_or("", |end| &body[end + 3..]);
+            continue;
+        }
+        let Some(end) = tag_end(rest) else {
+            break;
+        };
+        let raw = &rest[..end];
+        rest = &rest[end + 1..];
+
+        if raw.starts_with('?') || raw.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = raw.strip_prefix('/') {
+            if name.trim() == "defs" && defs_depth > 0 {
+                defs_depth -= 1;
+            }
+            continue;
+        }
+
+        let self_closing = raw.ends_with('/');
+        let raw = raw.trim_end_matches('/');
+        let name_end = raw.find(|c: char| c.is_whitespace()).unwrap_or(raw.len());
+        let name = raw[..name_end].to_string();
+        if name == "defs" {
+            if !self_closing {
+                defs_depth += 1;
+            }
+            continue;
+        }
+        if defs_depth == 0 {
+            tags.push(Tag {
+                name,
+                attrs: parse_attrs(&raw[name_end..]),
+            });
+        }
+    }
+    tags
+}
+
+/// 따옴표 안의 '>'를 건너뛰고 태그 끝 위치 찾기
+fn tag_end(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// name="value" 속성 목록
+fn parse_attrs(s: &str) -> HashMap<String, String> {
+    let mut attrs = HashMap::new();
+    let mut rest = s;
+    while let Some(eq) = rest.find('=') {
+        let name = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let Some(quote) = after.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            break;
+        };
+        let Some(len) = after[1..].find(quote) else {
+            break;
+        };
+        attrs.insert(name, decode_entities(&after[1..1 + len]));
+        rest = &after[len + 2..];
+    }
+    attrs
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// 숫자 목록 ("10,20 30-5" → [10, 20, 30, -5])
+fn parse_numbers(s: &str) -> Vec<f64> {
+    let mut out = Vec::new();
+    let mut rest = s;
+    while let Some((n, len)) = next_number(rest) {
+        out.push(n);
+        rest = &rest[len..];
+    }
+    out
+}
+
+/// 문자열 앞쪽의 숫자 하나 → (값, 소비한 길이). 구분자(공백/쉼표)는 건너뜀.
+/// 범위를 넘는 지수(1e999 등)는 None
+fn next_number(s: &str) -> Option<(f64, usize)> {
+    let skip = s
+        .find(|c: char| !(c.is_whitespace() || c == ','))
+        .unwrap_or(s.len());
+    let bytes = s.as_bytes();
+    let mut i = skip;
+    if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+        i += 1;
+    }
+    let mut seen_dot = false;
+    let mut seen_digit = false;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'0'..=b'9' => seen_digit = true,
+            b'.' if !seen_dot => seen_dot = true,
+            b'e' | b'E' if seen_digit => {
+                // 지수부
+                i += 1;
+                if i < bytes.len() && (bytes[i] == b'-' || bytes[i] == b'+') {
+                    i += 1;
+                }
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                break;
+            }
+            _ => break,
+        }
+        i += 1;
+    }
+    if !seen_digit {
+        return None;
+    }
+    s[skip..i]
+        .parse()
+        .ok()
+        .filter(|n: &f64| n.is_finite())
+        .map(|n| (n, i))
+}
+
+/// 점 목록 속성 (polyline / polygon)
+fn parse_points(s: &str) -> Vec<Point> {
+    parse_numbers(s)
+        .chunks_exact(2)
+        .map(|c| Point { x: c[0], y: c[1] })
+        .collect()
+}
+
+/// path d 속성 → 하위 경로별 폴리라인 (곡선은 선분 근사)
+fn parse_path(d: &str) -> Vec<Vec<Point>> {
+    let mut paths: Vec<Vec<Point>> = Vec::new();
+    let mut current: Vec<Point> = Vec::new();
+    let (mut x, mut y) = (0.0, 0.0);
+    let (mut start_x, mut start_y) = (0.0, 0.0);
+
+    let mut rest = d.trim_start();
+    let mut command = ' ';
+    while !rest.is_empty() {
+        let c = rest.chars().next().unwrap_or(' ');
+        if c.is_ascii_alphabetic() {
+            command = c;
+            rest = rest[1..].trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+            if command == 'Z' || command == 'z' {
+                if !current.is_empty() {
+                    current.push(Point {
+                        x: start_x,
+                        y: start_y,
+                    });
+                }
+                x = start_x;
+                y = start_y;
+            }
+            continue;
+        }
+
+        let arity = match command.to_ascii_uppercase() {
+            'M' | 'L' | 'T' => 2,
+            'H' | 'V' => 1,
+            'S' | 'Q' => 4,
+            'C' => 6,
+            'A' => 7,
+            _ => break,
+        };
+        let mut args = Vec::with_capacity(arity);
+        for _ in 0..arity {
+            let Some((n, len)) = next_number(rest) else {
+                break;
+            };
+            args.push(n);
+            rest = &rest[len..];
+        }
+        rest = rest.trim_start_matches(|ch: char| ch.is_whitespace() || ch == ',');
+        if args.len() < arity {
+            break;
+        }
+
+        let relative = command.is_ascii_lowercase();
+        let (ox, oy) = if relative { (x, y) } else { (0.0, 0.0) };
+        let from = Point { x, y };
+        match command.to_ascii_uppercase() {
+            'M' => {
+                if current.len() > 1 {
+                    paths.push(std::mem::take(&mut current));
+                }
+                current.clear();
+                x = ox + args[0];
+                y = oy + args[1];
+                start_x = x;
+                start_y = y;
+                current.push(Point { x, y });
+                // 이어지는 좌표쌍은 lineto
+                command = if relative { 'l' } else { 'L' };
+                continue;
+            }
+            'H' => x = ox + args[0],
+            'V' => y = if relative { y + args[0] } else { args[0] },
+            'C' => {
+                let c1 = Point {
+                    x: ox + args[0],
+                    y: oy + args[1],
+                };
+                let c2 = Point {
+                    x: ox + args[2],
+                    y: oy + args[3],
+                };
+                x = ox + args[4];
+                y = oy + args[5];
+                for i in 1..CURVE_STEPS {
+                    let t = i as f64 / CURVE_STEPS as f64;
+                    let u = 1.0 - t;
+                    current.push(Point {
+                        x: u * u * u * from.x
+                            + 3.0 * u * u * t * c1.x
+                            + 3.0 * u * t * t * c2.x
+                            + t * t * t * x,
+                        y: u * u * u * from.y
+                            + 3.0 * u * u * t * c1.y
+                            + 3.0 * u * t * t * c2.y
+                            + t * t * t * y,
+                    });
+                }
+            }
+            'Q' => {
+                let c = Point {
+                    x: ox + args[0],
+                    y: oy + args[1],
+                };
+                x = ox + args[2];
+                y = oy + args[3];
+                for i in 1..CURVE_STEPS {
+                    let t = i as f64 / CURVE_STEPS as f64;
+                    let u = 1.0 - t;
+                    current.push(Point {
+                        x: u * u * from.x + 2.0 * u * t * c.x + t * t * x,
+                        y: u * u * from.y + 2.0 * u * t * c.y + t * t * y,
+                    });
+                }
+            }
+            // L, T, S, A: 끝점으로 직선
+            _ => {
+                x = ox + args[arity - 2];
+                y = oy + args[arity - 1];
+            }
+        }
+        if current.is_empty() {
+            current.push(from);
+        }
+        current.push(Point { x, y });
+    }
+    if current.len() > 1 {
+        paths.push(current);
+    }
+    paths
+}
+
+/// 크기가 있는 도형인지 (너비 0인 사각형, 점 하나짜리 경로 등 제외)
+fn is_drawable(shape: &Shape) -> bool {
+    match shape {
+        Shape::Rect { w, h, .. } => *w > 0.0 && *h > 0.0,
+        Shape::Circle { r, .. } => *r > 0.0,
+        Shape::Freehand { points } => points.len() > 1,
+        _ => true,
+    }
+}
+
+/// SVG 텍스트를 요소 목록으로 (ID는 0, 붙여넣을 때 새로 부여)
+pub(crate) fn parse_svg(src: &str) -> Result<Vec<Element>, String> {
+    if !src.contains("<svg") {
+        return Err("not an SVG document".to_string());
+    }
+
+    let mut elements = Vec::new();
+    for tag in scan_tags(src) {
+        let Some(style) = tag.style() else {
+            continue;
+        };
+        let shapes = match tag.name.as_str() {
+            "line" => vec![Shape::Line {
+                x1: tag.num("x1"),
+                y1: tag.num("y1"),
+                x2: tag.num("x2"),
+                y2: tag.num("y2"),
+            }],
+            "rect" => vec![Shape::Rect {
+                x: tag.num("x"),
+                y: tag.num("y"),
+                w: tag.num("width"),
+                h: tag.num("height"),
+            }],
+            "circle" => vec![Shape::Circle {
+                cx: tag.num("cx"),
+                cy: tag.num("cy"),
+                r: tag.num("r"),
+            }],
+            "polyline" | "polygon" => {
+                let mut points = parse_points(tag.attrs.get("points").map_or("", |s| s));
+                if tag.name == "polygon" {
+                    if let Some(first) = points.first().cloned() {
+                        points.push(first);
+                    }
+                }
+                vec![Shape::Freehand { points }]
+            }
+            "path" => parse_path(tag.attrs.get("d").map_or("", |s| s))
+                .into_iter()
+                .map(|points| Shape::Freehand { points })
+                .collect(),
+            _ => Vec::new(),
+        };
+        elements.extend(shapes.into_iter().filter(is_drawable).map(|shape| Element {
+            id: 0,
+            shape,
+            style: style.clone(),
+            layer: 0,
+            locked: false,
+            hidden: false,
+        }));
+    }
+    Ok(elements)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_finite_lengths_are_rejected() {
+        for s in ["inf", "-inf", "infinity", "NaN", "1e999"] {
+            assert_eq!(parse_length(s), None, "{}", s);
+        }
+        assert_eq!(parse_length(" 12.5px "), Some(12.5));
+    }
+
+    #[test]
+    fn non_finite_numbers_end_the_list() {
+        assert_eq!(parse_numbers("10,20 30-5"), vec![10.0, 20.0, 30.0, -5.0]);
+        assert_eq!(parse_numbers("1 2 1e999 4"), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn non_finite_attributes_are_not_imported() {
+        let src = r#"<svg>
+            <rect x="0" y="0" width="inf" height="10" stroke="black"/>
+            <circle cx="NaN" cy="0" r="5" stroke="black" stroke-width="inf"/>
+            <path d="M 0 0 L 1e999 10" stroke="black"/>
+        </svg>"#;
+        let elements = parse_svg(src).unwrap();
+        assert_eq!(elements.len(), 1);
+        let e = &elements[0];
+        assert!(matches!(e.shape, Shape::Circle { cx, r, .. } if cx == 0.0 && r == 5.0));
+        assert_eq!(e.style.width, DEFAULT_WIDTH);
+    }
+}
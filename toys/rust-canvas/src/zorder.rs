@@ -0,0 +1,205 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::layers::layer_index;
+use crate::models::Element;
+use crate::{Action, CanvasInner};
+
+// ===== Z-order (그리기 순서) =====
+
+/// 요소 목록을 주어진 ID 순서로 재배열 (목록에 없는 ID는 맨 뒤, 원래 순서 유지)
+pub(crate) fn reorder(elements: &mut [Element], order: &[u32]) {
+    let rank: HashMap<u32, usize> = order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    elements.sort_by_key(|e| rank.get(&e.id).copied().unwrap_or(usize::MAX));
+}
+
+/// Z-order 변경 종류
+#[derive(Clone, Copy)]
+pub(crate) enum ZOrder {
+    /// 맨 앞으로
+    Front,
+    /// 맨 뒤로
+    Back,
+    /// 한 단계 앞으로
+    Forward,
+    /// 한 단계 뒤로
+    Backward,
+}
+
+impl ZOrder {
+    pub(crate) fn parse(s: &str) -> Option<ZOrder> {
+        match s {
+            "front" => Some(ZOrder::Front),
+            "back" => Some(ZOrder::Back),
+            "forward" => Some(ZOrder::Forward),
+            "backward" => Some(ZOrder::Backward),
+            _ => None,
+        }
+    }
+
+    /// 새 ID 순서 계산 (선택된 요소끼리의 상대 순서 유지).
+    /// layers\[i\]는 order\[i\]가 그려지는 레이어 — 한 단계 이동은 같은 레이어의 이웃과만 교환
+    fn apply(&self, order: &[u32], layers: &[usize], selected: &HashSet<u32>) -> Vec<u32> {
+        let (sel, rest): (Vec<u32>, Vec<u32>) = order.iter().partition(|id| selected.contains(id));
+        match self {
+            ZOrder::Front => rest.into_iter().chain(sel).collect(),
+            ZOrder::Back => sel.into_iter().chain(rest).collect(),
+            ZOrder::Forward => {
+                // 위에서부터: 같은 레이어의 바로 위 요소가 비선택이면 자리 교환
+                let (mut order, mut layers) = (order.to_vec(), layers.to_vec());
+                for i in (0..order.len()).rev() {
+                    if !selected.contains(&order[i]) {
+                        continue;
+                    }
+                    if let Some(j) = (i + 1..order.len()).find(|&j| layers[j] == layers[i]) {
+                        if !selected.contains(&order[j]) {
+                            order.swap(i, j);
+                            layers.swap(i, j);
+                        }
+                    }
+                }
+                order
+            }
+            ZOrder::Backward => {
+                let (mut order, mut layers) = (order.to_vec(), layers.to_vec());
+                for i in 0..order.len() {
+                    if !selected.contains(&order[i]) {
+                        continue;
+                    }
+                    if let Some(j) = (0..i).rev().find(|&j| layers[j] == layers[i]) {
+                        if !selected.contains(&order[j]) {
+                            order.swap(i, j);
+                            layers.swap(i, j);
+                        }
+                    }
+                }
+                order
+            }
+        }
+    }
+}
+
+/// 레이어별 그리기 순서가 같은지 (다른 레이어끼리의 순서는 화면에 영향 없음)
+fn same_stacking(before: &[u32], after: &[u32], layer: &HashMap<u32, usize>) -> bool {
+    let per_layer = |order: &[u32]| {
+        let mut stacks: HashMap<usize, Vec<u32>> = HashMap::new();
+        for id in order {
+            stacks.entry(layer[id]).or_default().push(*id);
+        }
+        stacks
+    };
+    per_layer(before) == per_layer(after)
+}
+
+impl CanvasInner {
+    /// 선택 요소의 그리기 순서 변경 (mode: "front" | "back" | "forward" | "backward").
+    /// 순서가 바뀌었으면 true
+    pub(crate) fn reorder_selected(&mut self, mode: &str) -> Result<bool, String> {
+        let mode = ZOrder::parse(mode).ok_or_else(|| format!("unknown z-order: {}", mode))?;
+        if self.selected_ids.is_empty() {
+            return Ok(false);
+        }
+
+        let before: Vec<u32> = self.scope().iter().map(|e| e.id).collect();
+        // 그룹 안의 자식은 그룹과 함께 그려지므로 하나의 레이어로 취급
+        let layers: Vec<usize> = if self.entered_groups.is_empty() {
+            self.scope()
+                .iter()
+                .map(|e| layer_index(&self.layers, e.layer))
+                .collect()
+        } else {
+            vec![0; before.len()]
+        };
+        let after = mode.apply(&before, &layers, &self.selected_ids);
+        let layer: HashMap<u32, usize> = before.iter().copied().zip(layers).collect();
+        if same_stacking(&before, &after, &layer) {
+            return Ok(false);
+        }
+
+        let action = self.scoped(Action::ReorderElements { before, after });
+        action.apply(&mut self.elements);
+        self.push_action(action);
+        self.needs_render = true;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{Element, Shape, Style};
+    use crate::CanvasInner;
+
+    fn rect(id: u32, layer: u32) -> Element {
+        Element {
+            id,
+            shape: Shape::Rect {
+                x: 0.0,
+                y: 0.0,
+                w: 10.0,
+                h: 10.0,
+            },
+            style: Style {
+                color: "#000000".to_string(),
+                width: 1.0,
+            },
+            layer,
+            locked: false,
+            hidden: false,
+        }
+    }
+
+    fn ids(c: &CanvasInner) -> Vec<u32> {
+        c.elements.iter().map(|e| e.id).collect()
+    }
+
+    /// 레이어 0: 1, 3 / 레이어 L: 2, 4 (목록 순서 1, 2, 3, 4)
+    fn canvas() -> CanvasInner {
+        let mut c = CanvasInner::for_test();
+        let top = c.add_layer("top");
+        c.elements = vec![rect(1, 0), rect(2, top), rect(3, 0), rect(4, top)];
+        c.next_id = 5;
+        c
+    }
+
+    #[test]
+    fn forward_swaps_with_next_element_on_same_layer() {
+        let mut c = canvas();
+        c.selected_ids.insert(1);
+        assert_eq!(c.reorder_selected("forward"), Ok(true));
+        assert_eq!(ids(&c), vec![3, 2, 1, 4]);
+
+        c.undo();
+        assert_eq!(ids(&c), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn backward_swaps_with_previous_element_on_same_layer() {
+        let mut c = canvas();
+        c.selected_ids.insert(4);
+        assert_eq!(c.reorder_selected("backward"), Ok(true));
+        assert_eq!(ids(&c), vec![1, 4, 3, 2]);
+    }
+
+    #[test]
+    fn reorder_without_visible_change_is_not_recorded() {
+        let mut c = canvas();
+        // 3은 이미 레이어 0의 맨 위, 2는 상위 레이어의 맨 아래
+        c.selected_ids.insert(3);
+        assert_eq!(c.reorder_selected("forward"), Ok(false));
+        assert_eq!(c.reorder_selected("front"), Ok(false));
+        c.selected_ids = [2].into();
+        assert_eq!(c.reorder_selected("backward"), Ok(false));
+        assert_eq!(c.reorder_selected("back"), Ok(false));
+
+        assert_eq!(ids(&c), vec![1, 2, 3, 4]);
+        assert!(c.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn front_keeps_relative_order_of_selection() {
+        let mut c = canvas();
+        c.selected_ids = [1, 2].into();
+        assert_eq!(c.reorder_selected("front"), Ok(true));
+        assert_eq!(ids(&c), vec![3, 4, 1, 2]);
+        assert!(c.reorder_selected("sideways").is_err());
+    }
+}